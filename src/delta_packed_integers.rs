@@ -0,0 +1,324 @@
+use crate::{PackedInt, PackedIntegers, PackedValue, UnsortedError};
+use std::fmt::{self, Debug, Formatter};
+
+/// A growable array of non-decreasing integers, stored as packed gaps between consecutive values.
+///
+/// Values are read and written as `u64`, and each gap must fit in `T`. The absolute value of every
+/// `sample_rate`-th integer is kept alongside the gaps, so that `get` only needs to sum at most
+/// `sample_rate - 1` gaps.
+///
+/// # Examples
+///
+/// ```
+/// use packed_integers::{DeltaPackedIntegers, U4};
+///
+/// let mut is = DeltaPackedIntegers::<U4>::with_sample_rate(4);
+/// for i in &[1_000_000, 1_000_003, 1_000_003, 1_000_010, 1_000_025] {
+///     is.push(*i).unwrap();
+/// }
+///
+/// assert_eq!(is.get(3), Some(1_000_010));
+///
+/// // Fails, as values must not decrease.
+/// assert!(is.push(1_000_024).is_err());
+///
+/// // This will panic, as the gap of 16 > U4::MAX.
+/// // is.push(1_000_041);
+/// ```
+#[derive(Clone)]
+pub struct DeltaPackedIntegers<T: PackedInt> {
+    gaps: PackedIntegers<T>,
+    samples: Vec<u64>,
+    sample_rate: usize,
+    last: u64,
+}
+
+impl<T: PackedInt> Default for DeltaPackedIntegers<T> {
+    fn default() -> DeltaPackedIntegers<T> {
+        DeltaPackedIntegers::new()
+    }
+}
+
+impl<T: PackedInt> DeltaPackedIntegers<T> {
+    const DEFAULT_SAMPLE_RATE: usize = 64;
+
+    /// Constructs a new, empty `DeltaPackedIntegers<T>`, sampling every 64th integer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{DeltaPackedIntegers, U8};
+    ///
+    /// let mut is = DeltaPackedIntegers::<U8>::new();
+    ///
+    /// assert_eq!(is.sample_rate(), 64);
+    /// ```
+    pub fn new() -> DeltaPackedIntegers<T> {
+        DeltaPackedIntegers::with_sample_rate(Self::DEFAULT_SAMPLE_RATE)
+    }
+
+    /// Constructs a new, empty `DeltaPackedIntegers<T>`, sampling every `sample_rate`-th integer.
+    /// Panics if `sample_rate` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{DeltaPackedIntegers, U8};
+    ///
+    /// let mut is = DeltaPackedIntegers::<U8>::with_sample_rate(16);
+    ///
+    /// assert_eq!(is.sample_rate(), 16);
+    /// ```
+    pub fn with_sample_rate(sample_rate: usize) -> DeltaPackedIntegers<T> {
+        if sample_rate == 0 {
+            panic!("sample rate should be > 0");
+        }
+
+        DeltaPackedIntegers {
+            gaps: PackedIntegers::new(),
+            samples: Vec::new(),
+            sample_rate,
+            last: 0,
+        }
+    }
+
+    /// Clears the vector. This method does not affect the vector's allocated capacity.
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Returns the value of the integer at position `index`, or `None` if out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{DeltaPackedIntegers, U8};
+    ///
+    /// let mut is = DeltaPackedIntegers::<U8>::new();
+    /// is.push(100).unwrap();
+    /// is.push(300).unwrap();
+    ///
+    /// assert_eq!(is.get(1), Some(300));
+    /// assert_eq!(is.get(2), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<u64> {
+        if index >= self.gaps.len() {
+            return None;
+        }
+
+        let sample = index / self.sample_rate;
+        let mut value = self.samples[sample];
+        for i in (sample * self.sample_rate + 1)..=index {
+            value += self.gaps.get_unchecked(i).into();
+        }
+        Some(value)
+    }
+
+    /// Returns the packed gaps between consecutive integers. Gaps at sampled positions are 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, DeltaPackedIntegers, U8};
+    ///
+    /// let mut is = DeltaPackedIntegers::<U8>::with_sample_rate(2);
+    /// for i in &[1000, 1010, 1030, 1060] {
+    ///     is.push(*i).unwrap();
+    /// }
+    ///
+    /// assert_eq!(is.gaps(), &packed_ints![0, 10, 0, 30; U8]);
+    /// ```
+    pub fn gaps(&self) -> &PackedIntegers<T> {
+        &self.gaps
+    }
+
+    /// Returns `true` if the vector contains no integers.
+    pub fn is_empty(&self) -> bool {
+        self.gaps.is_empty()
+    }
+
+    /// Returns an iterator over the vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{DeltaPackedIntegers, U8};
+    ///
+    /// let mut is = DeltaPackedIntegers::<U8>::new();
+    /// is.push(100).unwrap();
+    /// is.push(300).unwrap();
+    /// let mut iter = is.iter();
+    ///
+    /// assert_eq!(iter.next(), Some(100));
+    /// assert_eq!(iter.next(), Some(300));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn iter(&self) -> DeltaPackedIntegersIterator<'_, T> {
+        self.into_iter()
+    }
+
+    /// Returns the last integer in the vector, or `None` if empty.
+    pub fn last(&self) -> Option<u64> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.last)
+        }
+    }
+
+    /// Returns the number of integers in the vector.
+    pub fn len(&self) -> usize {
+        self.gaps.len()
+    }
+
+    /// Removes the last integer from the vector and returns it, or `None` if empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{DeltaPackedIntegers, U8};
+    ///
+    /// let mut is = DeltaPackedIntegers::<U8>::new();
+    /// is.push(100).unwrap();
+    /// is.push(300).unwrap();
+    ///
+    /// assert_eq!(is.pop(), Some(300));
+    /// assert_eq!(is.last(), Some(100));
+    /// ```
+    pub fn pop(&mut self) -> Option<u64> {
+        let result = self.last();
+        if result.is_some() {
+            self.truncate(self.len() - 1);
+        }
+        result
+    }
+
+    /// Appends an integer to the back of the vector. Fails if `value` is less than the last
+    /// integer, and panics if the gap between them is greater than `T::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{DeltaPackedIntegers, U8};
+    ///
+    /// let mut is = DeltaPackedIntegers::<U8>::new();
+    /// is.push(100).unwrap();
+    /// is.push(355).unwrap();
+    ///
+    /// let err = is.push(354).unwrap_err();
+    /// assert_eq!(err.index, 2);
+    /// assert_eq!(err.last, 355);
+    /// ```
+    pub fn push(&mut self, value: u64) -> Result<(), UnsortedError> {
+        let index = self.len();
+        if index > 0 && value < self.last {
+            return Err(UnsortedError {
+                index,
+                value,
+                last: self.last,
+            });
+        }
+
+        if index % self.sample_rate == 0 {
+            self.samples.push(value);
+            self.gaps.push(T::Value::from_u64(0));
+        } else {
+            let gap = value - self.last;
+            if gap > T::MAX.into() {
+                panic!("gap (is {}) is outside the range 0..={}", gap, T::MAX);
+            }
+            self.gaps.push(T::Value::from_u64(gap));
+        }
+
+        self.last = value;
+        Ok(())
+    }
+
+    /// Returns the number of integers between samples.
+    pub fn sample_rate(&self) -> usize {
+        self.sample_rate
+    }
+
+    /// Keeps the first `len` integers, and drops the rest.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{DeltaPackedIntegers, U8};
+    ///
+    /// let mut is = DeltaPackedIntegers::<U8>::new();
+    /// for i in &[100, 200, 300] {
+    ///     is.push(*i).unwrap();
+    /// }
+    /// is.truncate(2);
+    ///
+    /// assert_eq!(is.iter().collect::<Vec<_>>(), vec![100, 200]);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len() {
+            return;
+        }
+
+        self.gaps.truncate(len);
+        self.samples.truncate(len.div_ceil(self.sample_rate));
+        if len > 0 {
+            self.last = self.get(len - 1).unwrap();
+        }
+    }
+}
+
+/// An iterator for `DeltaPackedIntegers`.
+pub struct DeltaPackedIntegersIterator<'a, T: PackedInt> {
+    vec: &'a DeltaPackedIntegers<T>,
+    index: usize,
+    value: u64,
+}
+
+impl<'a, T: PackedInt> IntoIterator for &'a DeltaPackedIntegers<T> {
+    type Item = u64;
+    type IntoIter = DeltaPackedIntegersIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DeltaPackedIntegersIterator {
+            vec: self,
+            index: 0,
+            value: 0,
+        }
+    }
+}
+
+impl<T: PackedInt> Iterator for DeltaPackedIntegersIterator<'_, T> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.vec.len() {
+            return None;
+        }
+
+        // Keep a running total rather than starting again from the nearest sample.
+        if self.index % self.vec.sample_rate == 0 {
+            self.value = self.vec.samples[self.index / self.vec.sample_rate];
+        } else {
+            self.value += self.vec.gaps.get_unchecked(self.index).into();
+        }
+        self.index += 1;
+
+        Some(self.value)
+    }
+}
+
+impl<T: PackedInt> Eq for DeltaPackedIntegers<T> {}
+
+impl<T: PackedInt> PartialEq for DeltaPackedIntegers<T> {
+    /// Compares the integers held, regardless of each vector's sample rate.
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: PackedInt> Debug for DeltaPackedIntegers<T> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "(U{}, delta) ", T::NUM_BITS)?;
+        formatter.debug_list().entries(self.iter()).finish()
+    }
+}
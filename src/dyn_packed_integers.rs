@@ -0,0 +1,673 @@
+use crate::{raw, PackedInt, PackedIntegers, PackedValue, Word};
+use std::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
+use std::convert::TryFrom;
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+
+/// A growable array of packed integers whose width is chosen at runtime, backed by a `Vec<u32>`
+/// buffer.
+///
+/// `DynPackedIntegers` offers the same API as `PackedIntegers`, but stores its width in the range
+/// `1..=64` as a field rather than as a type parameter. Values are read and written as `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use packed_integers::DynPackedIntegers;
+///
+/// let max: u32 = 300;
+/// let num_bits = (u32::BITS - max.leading_zeros()) as usize;
+///
+/// let mut is = DynPackedIntegers::new(num_bits);
+/// is.push(299);
+/// is.push(300);
+///
+/// assert_eq!(is.num_bits(), 9);
+/// assert_eq!(is.get(1), Some(300));
+///
+/// // This will panic, as 512 > 2^9 - 1.
+/// // is.push(512);
+/// ```
+///
+/// Converting to and from `PackedIntegers` reuses the backing buffer:
+///
+/// ```
+/// use packed_integers::{packed_ints, DynPackedIntegers, PackedIntegers, U9, U10};
+/// use std::convert::TryFrom;
+///
+/// let is = DynPackedIntegers::from(packed_ints![509, 510, 511; U9]);
+/// assert_eq!(is.num_bits(), 9);
+///
+/// // Fails, as the widths do not match. The original vector is handed back.
+/// let is = PackedIntegers::<U10>::try_from(is).unwrap_err();
+///
+/// let is = PackedIntegers::<U9>::try_from(is).unwrap();
+/// assert_eq!(is, packed_ints![509, 510, 511; U9]);
+/// ```
+#[derive(Clone)]
+pub struct DynPackedIntegers {
+    buf: Vec<u32>,
+    len: usize,
+    num_bits: usize,
+}
+
+impl DynPackedIntegers {
+    /// Constructs a new, empty `DynPackedIntegers` holding `num_bits`-bit integers. Panics if
+    /// `num_bits` is outside the range `1..=64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let mut is = DynPackedIntegers::new(9);
+    /// ```
+    pub fn new(num_bits: usize) -> DynPackedIntegers {
+        Self::check_num_bits(num_bits);
+
+        DynPackedIntegers {
+            buf: Vec::new(),
+            len: 0,
+            num_bits,
+        }
+    }
+
+    /// Constructs a new, empty `DynPackedIntegers` holding `num_bits`-bit integers with _at least_
+    /// the specified capacity. Panics if `num_bits` is outside the range `1..=64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let mut is = DynPackedIntegers::with_capacity(8, 1);
+    ///
+    /// // The specified capacity is 1, but because `DynPackedIntegers` is backed by a `Vec<u32>`
+    /// // buffer, it will actually hold 4 8-bit integers without reallocating.
+    /// assert_eq!(is.capacity(), 4);
+    /// ```
+    pub fn with_capacity(num_bits: usize, capacity: usize) -> DynPackedIntegers {
+        Self::check_num_bits(num_bits);
+
+        DynPackedIntegers {
+            buf: Vec::with_capacity(raw::buf_len::<u32>(capacity, num_bits)),
+            len: 0,
+            num_bits,
+        }
+    }
+
+    /// Moves all integers of `other` into `Self`, leaving `other` empty. Panics if the widths of
+    /// the two vectors differ.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let mut is1 = DynPackedIntegers::from_slice(12, &[10, 20, 30]);
+    /// let mut is2 = DynPackedIntegers::from_slice(12, &[40, 50, 60]);
+    /// is1.append(&mut is2);
+    ///
+    /// assert_eq!(is1, DynPackedIntegers::from_slice(12, &[10, 20, 30, 40, 50, 60]));
+    /// assert!(is2.is_empty());
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        if self.num_bits != other.num_bits {
+            panic!(
+                "widths should match (is {}, other is {})",
+                self.num_bits, other.num_bits
+            );
+        }
+
+        self.reserve(other.len);
+
+        for i in other.iter() {
+            self.push(i);
+        }
+        other.clear();
+    }
+
+    /// Returns the number of integers the vector can hold without reallocating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let mut is = DynPackedIntegers::with_capacity(16, 3);
+    ///
+    /// assert_eq!(is.capacity(), 4);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity() * <u32 as Word>::NUM_BITS / self.num_bits
+    }
+
+    /// Clears the vector. This method does not affect the vector's allocated capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let mut is = DynPackedIntegers::from_slice(9, &[100, 200, 300]);
+    ///
+    /// is.clear();
+    ///
+    /// assert!(is.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Creates an array of `num_bits`-bit packed integers from a slice of values. Panics if
+    /// `num_bits` is outside the range `1..=64`, or if any value does not fit in `num_bits` bits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let is = DynPackedIntegers::from_slice(40, &[1 << 39, 1 << 38]);
+    ///
+    /// assert_eq!(is.get(0), Some(1 << 39));
+    /// assert_eq!(is.get(1), Some(1 << 38));
+    /// ```
+    pub fn from_slice(num_bits: usize, values: &[u64]) -> DynPackedIntegers {
+        let mut is = DynPackedIntegers::with_capacity(num_bits, values.len());
+        for &value in values {
+            is.push(value);
+        }
+        is
+    }
+
+    /// Creates an array of packed integers from a slice of values, using the smallest width that
+    /// can hold the largest value. An empty slice, or one containing only zeroes, results in a
+    /// width of 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let is = DynPackedIntegers::from_slice_auto(&[3u32, 300, 30]);
+    ///
+    /// assert_eq!(is.num_bits(), 9);
+    /// assert_eq!(is, DynPackedIntegers::from_slice(9, &[3, 300, 30]));
+    ///
+    /// let is = DynPackedIntegers::from_slice_auto(&[1u64 << 40]);
+    ///
+    /// assert_eq!(is.num_bits(), 41);
+    /// ```
+    pub fn from_slice_auto<V: PackedValue>(values: &[V]) -> DynPackedIntegers {
+        let max = values.iter().map(|&v| v.into()).max().unwrap_or(0);
+        let num_bits = (64 - max.leading_zeros() as usize).max(1);
+
+        let mut buf = vec![0; raw::buf_len::<u32>(values.len(), num_bits)];
+        for (i, &value) in values.iter().enumerate() {
+            raw::write(&mut buf, i * num_bits, num_bits, value.into());
+        }
+
+        DynPackedIntegers {
+            buf,
+            len: values.len(),
+            num_bits,
+        }
+    }
+
+    /// Creates an array of `num_bits`-bit packed integers from a supplied `Vec<u32>` buffer.
+    /// Panics if `num_bits` is outside the range `1..=64`, or if `num_ints * num_bits` is greater
+    /// than the number of bits the buffer has.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let buf = vec![0b00000001_00000010_00000100_00001000];
+    /// let is = DynPackedIntegers::from_vec(8, buf, 4);
+    ///
+    /// assert_eq!(is.get(0), Some(0b1000));
+    /// assert_eq!(is.get(1), Some(0b0100));
+    /// assert_eq!(is.get(2), Some(0b0010));
+    /// assert_eq!(is.get(3), Some(0b0001));
+    /// assert_eq!(is.get(4), None);
+    /// ```
+    pub fn from_vec(num_bits: usize, buf: Vec<u32>, num_ints: usize) -> DynPackedIntegers {
+        Self::check_num_bits(num_bits);
+
+        let min_len = raw::buf_len::<u32>(num_ints, num_bits);
+        if buf.len() < min_len {
+            panic!(
+                "vector is too small (len {}, should be at least {})",
+                buf.len(),
+                min_len
+            );
+        }
+
+        DynPackedIntegers {
+            buf,
+            len: num_ints,
+            num_bits,
+        }
+    }
+
+    /// Returns the value of the integer at position `index`, or `None` if out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let is = DynPackedIntegers::from_slice(9, &[100, 200, 300]);
+    ///
+    /// assert_eq!(is.get(1), Some(200));
+    /// assert_eq!(is.get(3), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<u64> {
+        if index >= self.len {
+            None
+        } else {
+            Some(self.get_unchecked(index))
+        }
+    }
+
+    fn get_unchecked(&self, index: usize) -> u64 {
+        raw::read(&self.buf, index * self.num_bits, self.num_bits)
+    }
+
+    /// Inserts an integer at position `index`, shifting all integers after it to the right.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let mut is = DynPackedIntegers::from_slice(8, &[10, 20, 30]);
+    ///
+    /// is.insert(1, 40);
+    /// assert_eq!(is, DynPackedIntegers::from_slice(8, &[10, 40, 20, 30]));
+    /// ```
+    pub fn insert(&mut self, index: usize, value: u64) {
+        if index > self.len {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                index, self.len
+            );
+        }
+
+        self.push(value);
+        for i in ((index + 1)..self.len).rev() {
+            self.set_unchecked(i, self.get_unchecked(i - 1))
+        }
+        self.set_unchecked(index, value);
+    }
+
+    /// Returns `true` if the vector contains no integers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let mut is = DynPackedIntegers::new(8);
+    /// assert!(is.is_empty());
+    ///
+    /// is.push(255);
+    /// assert!(!is.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let is = DynPackedIntegers::from_slice(9, &[509, 510, 511]);
+    /// let mut iter = is.iter();
+    ///
+    /// assert_eq!(iter.next(), Some(509));
+    /// assert_eq!(iter.next(), Some(510));
+    /// assert_eq!(iter.next(), Some(511));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn iter(&self) -> DynPackedIntegersIterator<'_> {
+        self.into_iter()
+    }
+
+    /// Returns the number of integers in the vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let is = DynPackedIntegers::from_slice(9, &[507, 508, 509, 510, 511]);
+    ///
+    /// assert_eq!(is.len(), 5);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the largest value the vector can hold.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let is = DynPackedIntegers::new(9);
+    ///
+    /// assert_eq!(is.max_value(), 511);
+    /// ```
+    pub fn max_value(&self) -> u64 {
+        raw::max(self.num_bits)
+    }
+
+    /// Returns the number of bits in each integer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let is = DynPackedIntegers::new(9);
+    ///
+    /// assert_eq!(is.num_bits(), 9);
+    /// ```
+    pub fn num_bits(&self) -> usize {
+        self.num_bits
+    }
+
+    /// Removes the last integer from the vector and returns it, or `None` if empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let mut is = DynPackedIntegers::from_slice(10, &[100, 200, 300]);
+    ///
+    /// assert_eq!(is.pop(), Some(300));
+    /// assert_eq!(is, DynPackedIntegers::from_slice(10, &[100, 200]));
+    /// ```
+    pub fn pop(&mut self) -> Option<u64> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            Some(self.get_unchecked(self.len))
+        }
+    }
+
+    /// Appends an integer to the back of the vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let mut is = DynPackedIntegers::from_slice(10, &[100, 200]);
+    /// is.push(300);
+    ///
+    /// assert_eq!(is, DynPackedIntegers::from_slice(10, &[100, 200, 300]));
+    /// ```
+    pub fn push(&mut self, value: u64) {
+        if value > self.max_value() {
+            panic!("value is outside the range 0..={}", self.max_value());
+        }
+
+        let min_len = raw::buf_len::<u32>(self.len + 1, self.num_bits);
+        if self.buf.len() < min_len {
+            self.buf.resize(min_len, 0);
+        }
+
+        self.set_unchecked(self.len, value);
+        self.len += 1;
+    }
+
+    /// Removes and returns the integer at position `index`, shifting all integers after it to the
+    /// left.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let mut is = DynPackedIntegers::from_slice(8, &[10, 20, 30]);
+    ///
+    /// assert_eq!(is.remove(1), 20);
+    /// assert_eq!(is, DynPackedIntegers::from_slice(8, &[10, 30]));
+    /// ```
+    pub fn remove(&mut self, index: usize) -> u64 {
+        if index >= self.len {
+            panic!(
+                "removal index (is {}) should be < len (is {})",
+                index, self.len
+            );
+        }
+
+        let result = self.get_unchecked(index);
+
+        for i in (index + 1)..self.len {
+            self.set_unchecked(i - 1, self.get_unchecked(i));
+        }
+        self.len -= 1;
+
+        result
+    }
+
+    /// Reserves capacity for _at least_ `additional` more integers to be inserted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let mut is = DynPackedIntegers::from_slice(8, &[100]);
+    /// is.reserve(4);
+    ///
+    /// assert!(is.capacity() >= 5);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        if self.capacity() >= self.len + additional {
+            return;
+        }
+        let additional = raw::buf_len::<u32>(additional, self.num_bits);
+        self.buf.reserve(additional);
+    }
+
+    /// Sets the integer value at `index` to `value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let mut is = DynPackedIntegers::from_slice(9, &[100, 200, 300]);
+    /// is.set(1, 400);
+    ///
+    /// assert_eq!(is, DynPackedIntegers::from_slice(9, &[100, 400, 300]));
+    /// ```
+    pub fn set(&mut self, index: usize, value: u64) {
+        if index >= self.len {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
+            );
+        } else {
+            self.set_unchecked(index, value);
+        }
+    }
+
+    fn set_unchecked(&mut self, index: usize, value: u64) {
+        if value > self.max_value() {
+            panic!("value is outside the range 0..={}", self.max_value());
+        }
+
+        raw::write(&mut self.buf, index * self.num_bits, self.num_bits, value);
+    }
+
+    /// Returns a copy of the backing `Vec<u32>` buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let is = DynPackedIntegers::from_slice(8, &[0b0001, 0b0010, 0b0100, 0b1000]);
+    /// let vec = is.to_vec();
+    ///
+    /// assert_eq!(vec.len(), 1);
+    /// assert_eq!(vec[0], 0b00001000_00000100_00000010_00000001);
+    /// ```
+    pub fn to_vec(&self) -> Vec<u32> {
+        raw::to_vec(&self.buf, self.len * self.num_bits)
+    }
+
+    /// Keeps the first `len` integers, and drops the rest.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::DynPackedIntegers;
+    ///
+    /// let mut is = DynPackedIntegers::from_slice(9, &[100, 200, 300, 400, 500]);
+    /// is.truncate(2);
+    ///
+    /// assert_eq!(is, DynPackedIntegers::from_slice(9, &[100, 200]));
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len > self.len {
+            return;
+        }
+        self.len = len;
+    }
+
+    fn check_num_bits(num_bits: usize) {
+        if num_bits == 0 || num_bits > 64 {
+            panic!("width (is {}) should be in the range 1..=64", num_bits);
+        }
+    }
+}
+
+impl<T: PackedInt> From<PackedIntegers<T>> for DynPackedIntegers {
+    fn from(is: PackedIntegers<T>) -> DynPackedIntegers {
+        DynPackedIntegers {
+            buf: is.buf,
+            len: is.len,
+            num_bits: T::NUM_BITS,
+        }
+    }
+}
+
+impl<T: PackedInt> TryFrom<DynPackedIntegers> for PackedIntegers<T> {
+    type Error = DynPackedIntegers;
+
+    /// Converts a `DynPackedIntegers` into a `PackedIntegers<T>`, handing the original vector
+    /// back if its width is not `T::NUM_BITS`.
+    fn try_from(is: DynPackedIntegers) -> Result<PackedIntegers<T>, DynPackedIntegers> {
+        if is.num_bits != T::NUM_BITS {
+            return Err(is);
+        }
+
+        Ok(PackedIntegers {
+            buf: is.buf,
+            len: is.len,
+            phantom: PhantomData,
+        })
+    }
+}
+
+/// A consuming iterator for `DynPackedIntegers`.
+pub struct DynPackedIntegersIntoIterator {
+    vec: DynPackedIntegers,
+    index: usize,
+}
+
+impl IntoIterator for DynPackedIntegers {
+    type Item = u64;
+    type IntoIter = DynPackedIntegersIntoIterator;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DynPackedIntegersIntoIterator {
+            vec: self,
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for DynPackedIntegersIntoIterator {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.vec.get(self.index);
+        self.index += 1;
+
+        result
+    }
+}
+
+/// An iterator for `DynPackedIntegers`.
+pub struct DynPackedIntegersIterator<'a> {
+    vec: &'a DynPackedIntegers,
+    index: usize,
+}
+
+impl<'a> IntoIterator for &'a DynPackedIntegers {
+    type Item = u64;
+    type IntoIter = DynPackedIntegersIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DynPackedIntegersIterator {
+            vec: self,
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for DynPackedIntegersIterator<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.vec.get(self.index);
+        self.index += 1;
+
+        result
+    }
+}
+
+impl Eq for DynPackedIntegers {}
+
+impl PartialEq for DynPackedIntegers {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Ord for DynPackedIntegers {
+    /// Compares vectors by width first, then lexicographically by value.
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.num_bits.cmp(&other.num_bits) {
+            Ordering::Equal => self.iter().cmp(other.iter()),
+            cmp => cmp,
+        }
+    }
+}
+
+impl PartialOrd for DynPackedIntegers {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Debug for DynPackedIntegers {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "(U{}) ", self.num_bits)?;
+        formatter.debug_list().entries(self.iter()).finish()
+    }
+}
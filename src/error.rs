@@ -0,0 +1,98 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// The error returned when repacking integers into a type too narrow to hold them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RepackError {
+    /// The position of the first integer that does not fit.
+    pub index: usize,
+    /// The value of the first integer that does not fit.
+    pub value: u64,
+    /// The largest value the target type can hold.
+    pub max: u64,
+}
+
+impl Display for RepackError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "value at index {} (is {}) is outside the range 0..={}",
+            self.index, self.value, self.max
+        )
+    }
+}
+
+impl Error for RepackError {}
+
+/// The error returned when pushing a value smaller than the last value of a sorted sequence.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UnsortedError {
+    /// The position the value would have been pushed to.
+    pub index: usize,
+    /// The value that was pushed.
+    pub value: u64,
+    /// The last value in the sequence, which `value` is smaller than.
+    pub last: u64,
+}
+
+impl Display for UnsortedError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "value at index {} (is {}) is less than the previous value (is {})",
+            self.index, self.value, self.last
+        )
+    }
+}
+
+impl Error for UnsortedError {}
+
+/// The error returned by the fallible methods of `PackedIntegers`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PackedIntegersError {
+    /// A value was greater than the largest value the integer type can hold.
+    ValueOutOfRange {
+        /// The value that does not fit.
+        value: u64,
+        /// The largest value the integer type can hold.
+        max: u64,
+    },
+    /// An index was past the end of the vector.
+    IndexOutOfBounds {
+        /// The index that was given.
+        index: usize,
+        /// The number of integers in the vector.
+        len: usize,
+    },
+    /// A buffer was too small to hold the requested number of integers.
+    BufferTooSmall {
+        /// The number of cells in the buffer.
+        len: usize,
+        /// The number of cells needed.
+        required: usize,
+    },
+}
+
+impl Display for PackedIntegersError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            PackedIntegersError::ValueOutOfRange { value, max } => write!(
+                formatter,
+                "value (is {}) is outside the range 0..={}",
+                value, max
+            ),
+            PackedIntegersError::IndexOutOfBounds { index, len } => write!(
+                formatter,
+                "index out of bounds: the len is {} but the index is {}",
+                len, index
+            ),
+            PackedIntegersError::BufferTooSmall { len, required } => write!(
+                formatter,
+                "vector is too small (len {}, should be at least {})",
+                len, required
+            ),
+        }
+    }
+}
+
+impl Error for PackedIntegersError {}
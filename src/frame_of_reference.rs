@@ -0,0 +1,338 @@
+use crate::{PackedInt, PackedIntegers, PackedIntegersIterator, PackedValue};
+use std::fmt::{self, Debug, Formatter};
+
+/// A growable array of packed integers stored as offsets from a base value.
+///
+/// Values are read and written as `u64`. Each value is stored as its difference from the base, so
+/// a `FrameOfReference<U9>` holds any values in the range `base..=base + 511`. Pushing or setting a
+/// value below the base lowers the base to that value, repacking the existing integers.
+///
+/// # Examples
+///
+/// ```
+/// use packed_integers::{FrameOfReference, U9};
+///
+/// let mut is = FrameOfReference::<U9>::new(1_000_000);
+/// is.push(1_000_500);
+/// is.push(1_000_000);
+///
+/// assert_eq!(is.get(0), Some(1_000_500));
+///
+/// // Rebases to 999_990, as the value is below the current base.
+/// is.push(999_990);
+/// assert_eq!(is.base(), 999_990);
+/// assert_eq!(is.iter().collect::<Vec<_>>(), vec![1_000_500, 1_000_000, 999_990]);
+///
+/// // This will panic, as 1_000_500 is now more than U9::MAX above the base.
+/// // is.push(1_000_600);
+/// ```
+#[derive(Clone)]
+pub struct FrameOfReference<T: PackedInt> {
+    base: u64,
+    vec: PackedIntegers<T>,
+}
+
+impl<T: PackedInt> FrameOfReference<T> {
+    /// Constructs a new, empty `FrameOfReference<T>` with the given base.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{FrameOfReference, U9};
+    ///
+    /// let mut is = FrameOfReference::<U9>::new(1_000_000);
+    /// ```
+    pub fn new(base: u64) -> FrameOfReference<T> {
+        FrameOfReference {
+            base,
+            vec: PackedIntegers::new(),
+        }
+    }
+
+    /// Constructs a new, empty `FrameOfReference<T>` with the given base and _at least_ the
+    /// specified capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{FrameOfReference, U8};
+    ///
+    /// let mut is = FrameOfReference::<U8>::with_capacity(1_000_000, 1);
+    ///
+    /// assert_eq!(is.capacity(), 4);
+    /// ```
+    pub fn with_capacity(base: u64, capacity: usize) -> FrameOfReference<T> {
+        FrameOfReference {
+            base,
+            vec: PackedIntegers::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the base value that integers are stored relative to.
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// Returns the number of integers the vector can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    /// Clears the vector. This method does not affect the vector's allocated capacity or its base.
+    pub fn clear(&mut self) {
+        self.vec.clear()
+    }
+
+    /// Creates a frame of reference from a slice of values, using the smallest value as the base.
+    /// Panics if the difference between the smallest and largest values is greater than `T::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{FrameOfReference, U9};
+    ///
+    /// let is = FrameOfReference::<U9>::from_slice(&[1_000_300, 1_000_100, 1_000_500]);
+    ///
+    /// assert_eq!(is.base(), 1_000_100);
+    /// assert_eq!(is.get(2), Some(1_000_500));
+    /// ```
+    pub fn from_slice(values: &[u64]) -> FrameOfReference<T> {
+        let base = values.iter().copied().min().unwrap_or(0);
+
+        let mut is = FrameOfReference::with_capacity(base, values.len());
+        for &value in values {
+            is.push(value);
+        }
+        is
+    }
+
+    /// Returns the value of the integer at position `index`, or `None` if out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{FrameOfReference, U9};
+    ///
+    /// let is = FrameOfReference::<U9>::from_slice(&[1_000_100, 1_000_200]);
+    ///
+    /// assert_eq!(is.get(1), Some(1_000_200));
+    /// assert_eq!(is.get(2), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<u64> {
+        self.vec
+            .get(index)
+            .map(|offset| add_offset(self.base, offset))
+    }
+
+    /// Returns `true` if the vector contains no integers.
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// Returns an iterator over the vector.
+    pub fn iter(&self) -> FrameOfReferenceIterator<'_, T> {
+        self.into_iter()
+    }
+
+    /// Returns the number of integers in the vector.
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Returns the packed offsets of the integers from the base.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, FrameOfReference, U9};
+    ///
+    /// let is = FrameOfReference::<U9>::from_slice(&[1_000_100, 1_000_200]);
+    ///
+    /// assert_eq!(is.offsets(), &packed_ints![0, 100; U9]);
+    /// ```
+    pub fn offsets(&self) -> &PackedIntegers<T> {
+        &self.vec
+    }
+
+    /// Removes the last integer from the vector and returns it, or `None` if empty.
+    pub fn pop(&mut self) -> Option<u64> {
+        let base = self.base;
+        self.vec.pop().map(|offset| add_offset(base, offset))
+    }
+
+    /// Appends an integer to the back of the vector, rebasing if it is below the current base.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{FrameOfReference, U9};
+    ///
+    /// let mut is = FrameOfReference::<U9>::new(1_000_000);
+    /// is.push(1_000_511);
+    ///
+    /// assert_eq!(is.get(0), Some(1_000_511));
+    /// ```
+    pub fn push(&mut self, value: u64) {
+        let offset = self.offset(value, None);
+        self.vec.push(offset);
+    }
+
+    /// Changes the base to `base`, repacking the existing integers. Panics if any integer is below
+    /// `base`, or more than `T::MAX` above it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{FrameOfReference, U9};
+    ///
+    /// let mut is = FrameOfReference::<U9>::from_slice(&[1_000_100, 1_000_200]);
+    /// is.rebase(1_000_000);
+    ///
+    /// assert_eq!(is.base(), 1_000_000);
+    /// assert_eq!(is.offsets().get(0), Some(100));
+    /// assert_eq!(is.get(0), Some(1_000_100));
+    /// ```
+    pub fn rebase(&mut self, base: u64) {
+        self.rebase_except(base, None);
+    }
+
+    /// Reserves capacity for _at least_ `additional` more integers to be inserted.
+    pub fn reserve(&mut self, additional: usize) {
+        self.vec.reserve(additional)
+    }
+
+    /// Sets the integer value at `index` to `value`, rebasing if it is below the current base.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{FrameOfReference, U9};
+    ///
+    /// let mut is = FrameOfReference::<U9>::from_slice(&[1_000_100, 1_000_200]);
+    /// is.set(1, 1_000_050);
+    ///
+    /// assert_eq!(is.base(), 1_000_050);
+    /// assert_eq!(is.iter().collect::<Vec<_>>(), vec![1_000_100, 1_000_050]);
+    /// ```
+    pub fn set(&mut self, index: usize, value: u64) {
+        if index >= self.vec.len() {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.vec.len(),
+                index
+            );
+        }
+
+        let offset = self.offset(value, Some(index));
+        self.vec.set(index, offset);
+    }
+
+    /// Keeps the first `len` integers, and drops the rest.
+    pub fn truncate(&mut self, len: usize) {
+        self.vec.truncate(len)
+    }
+
+    /// Returns the offset to store `value` as, rebasing first if it is below the current base.
+    /// The integer at `skip`, if any, is about to be overwritten, so is left out of the rebase.
+    fn offset(&mut self, value: u64, skip: Option<usize>) -> T::Value {
+        if value < self.base {
+            self.rebase_except(value, skip);
+        }
+
+        let max: u64 = T::MAX.into();
+        if value - self.base > max {
+            panic!(
+                "value is outside the range {}..={}",
+                self.base,
+                self.base.saturating_add(max)
+            );
+        }
+
+        T::Value::from_u64(value - self.base)
+    }
+
+    /// Changes the base to `base`, as `rebase` does, ignoring the integer at `skip`. That integer
+    /// is left with an offset of zero.
+    fn rebase_except(&mut self, base: u64, skip: Option<usize>) {
+        let kept = || {
+            self.vec
+                .iter()
+                .enumerate()
+                .filter(move |&(i, _)| Some(i) != skip)
+                .map(|(_, offset)| add_offset(self.base, offset))
+        };
+        if let (Some(min), Some(max)) = (kept().min(), kept().max()) {
+            if min < base || max - base > T::MAX.into() {
+                panic!(
+                    "values in the range {}..={} do not fit a base of {}",
+                    min, max, base
+                );
+            }
+        }
+
+        for i in 0..self.vec.len() {
+            let offset = if Some(i) == skip {
+                0
+            } else {
+                add_offset(self.base, self.vec.get_unchecked(i)) - base
+            };
+            self.vec.set_unchecked(i, T::Value::from_u64(offset));
+        }
+        self.base = base;
+    }
+}
+
+/// Returns the value stored as `offset` from `base`. Panics if it does not fit a `u64`.
+#[inline]
+fn add_offset<V: PackedValue>(base: u64, offset: V) -> u64 {
+    match base.checked_add(offset.into()) {
+        Some(value) => value,
+        None => panic!(
+            "value (base {} + offset {}) overflows u64",
+            base,
+            offset.into()
+        ),
+    }
+}
+
+/// An iterator for `FrameOfReference`.
+pub struct FrameOfReferenceIterator<'a, T: PackedInt> {
+    iter: PackedIntegersIterator<'a, T>,
+    base: u64,
+}
+
+impl<'a, T: PackedInt> IntoIterator for &'a FrameOfReference<T> {
+    type Item = u64;
+    type IntoIter = FrameOfReferenceIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        FrameOfReferenceIterator {
+            iter: self.vec.iter(),
+            base: self.base,
+        }
+    }
+}
+
+impl<T: PackedInt> Iterator for FrameOfReferenceIterator<'_, T> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|offset| add_offset(self.base, offset))
+    }
+}
+
+impl<T: PackedInt> Eq for FrameOfReference<T> {}
+
+impl<T: PackedInt> PartialEq for FrameOfReference<T> {
+    /// Compares the integers held, regardless of each vector's base.
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: PackedInt> Debug for FrameOfReference<T> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "(U{}, base {}) ", T::NUM_BITS, self.base)?;
+        formatter.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -1,4 +1,4 @@
-//! `packed_integers` provides a growable array for integer types in the range `u1` to `u31`.
+//! `packed_integers` provides a growable array for integer types in the range `u1` to `u64`.
 //!
 //! # Use case
 //!
@@ -17,12 +17,37 @@
 
 use std::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
 use std::fmt::{self, Debug, Formatter};
+use std::iter::{FromIterator, FusedIterator};
 use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
 
+mod delta_packed_integers;
+mod dyn_packed_integers;
+mod error;
+mod frame_of_reference;
 mod packed_int;
+mod packed_integers_mut;
+mod packed_integers_ref;
+mod packed_signed_integers;
+mod packed_slice;
+mod packed_slice_mut;
+mod raw;
+mod set_ops;
+mod sort;
+mod word;
+pub use crate::delta_packed_integers::*;
+pub use crate::dyn_packed_integers::*;
+pub use crate::error::*;
+pub use crate::frame_of_reference::*;
 pub use crate::packed_int::*;
-
-/// A growable array of packed integers, backed by a `Vec<u32>` buffer.
+pub use crate::packed_integers_mut::*;
+pub use crate::packed_integers_ref::*;
+pub use crate::packed_signed_integers::*;
+pub use crate::packed_slice::*;
+pub use crate::packed_slice_mut::*;
+pub use crate::word::*;
+
+/// A growable array of packed integers, backed by a `Vec<W>` buffer of `u32` words by default.
 ///
 /// # Examples
 ///
@@ -59,17 +84,48 @@ pub use crate::packed_int::*;
 ///
 /// let mut is_u9 = packed_ints![509, 510, 511; U9];
 /// ```
+///
+/// The buffer's word type can be any of `u8`, `u16`, `u32` and `u64`:
+///
+/// ```
+/// use packed_integers::{PackedIntegers, U12};
+///
+/// let mut is = PackedIntegers::<U12, u8>::new();
+/// is.push(0xabc);
+/// is.push(0x123);
+///
+/// // Three bytes, with no padding.
+/// assert_eq!(is.to_vec(), vec![0xbc, 0x3a, 0x12]);
+/// ```
 #[derive(Clone)]
-pub struct PackedIntegers<T: PackedInt> {
-    buf: Vec<u32>,
+pub struct PackedIntegers<T: PackedInt, W: Word = u32> {
+    buf: Vec<W>,
     len: usize,
     phantom: PhantomData<T>,
 }
 
-impl<T: PackedInt> PackedIntegers<T> {
-    const U32_NUM_BITS: usize = 32;
+impl<T: PackedInt, W: Word> Default for PackedIntegers<T, W> {
+    fn default() -> PackedIntegers<T, W> {
+        PackedIntegers::new()
+    }
+}
+
+impl<T: PackedInt, W: Word> Extend<T::Value> for PackedIntegers<T, W> {
+    fn extend<I: IntoIterator<Item = T::Value>>(&mut self, iter: I) {
+        self.pack_iter(iter.into_iter());
+    }
+}
+
+impl<T: PackedInt, W: Word> FromIterator<T::Value> for PackedIntegers<T, W> {
+    fn from_iter<I: IntoIterator<Item = T::Value>>(iter: I) -> PackedIntegers<T, W> {
+        let mut is = PackedIntegers::new();
+        is.extend(iter);
+        is
+    }
+}
 
-    /// Constructs a new, empty `PackedIntegers<T>`.
+impl<T: PackedInt, W: Word> PackedIntegers<T, W> {
+    /// Constructs a new, empty `PackedIntegers<T, W>`.
     ///
     /// # Example
     ///
@@ -78,7 +134,7 @@ impl<T: PackedInt> PackedIntegers<T> {
     ///
     /// let mut is = PackedIntegers::<U9>::new();
     /// ```
-    pub fn new() -> PackedIntegers<T> {
+    pub fn new() -> PackedIntegers<T, W> {
         PackedIntegers {
             buf: Vec::new(),
             len: 0,
@@ -86,7 +142,7 @@ impl<T: PackedInt> PackedIntegers<T> {
         }
     }
 
-    /// Constructs a new, empty `PackedIntegers<T>` with _at least_ the specified capacity.
+    /// Constructs a new, empty `PackedIntegers<T, W>` with _at least_ the specified capacity.
     ///
     /// # Example
     ///
@@ -99,7 +155,7 @@ impl<T: PackedInt> PackedIntegers<T> {
     /// // buffer, it will actually hold 4 `U8`s without reallocating.
     /// assert_eq!(is.capacity(), 4);
     /// ```
-    pub fn with_capacity(capacity: usize) -> PackedIntegers<T> {
+    pub fn with_capacity(capacity: usize) -> PackedIntegers<T, W> {
         let capacity = Self::to_buf_capacity(capacity);
 
         PackedIntegers {
@@ -124,12 +180,30 @@ impl<T: PackedInt> PackedIntegers<T> {
     /// assert!(is2.is_empty());
     /// ```
     pub fn append(&mut self, other: &mut Self) {
-        self.reserve(other.len);
+        self.extend_from_packed(other);
+        other.clear();
+    }
 
-        for i in other.iter() {
-            self.push(i);
+    /// Searches a sorted vector for `value`, returning `Ok` with the position of a matching integer,
+    /// or `Err` with the position `value` could be inserted at to keep the vector sorted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let is = packed_ints![100, 200, 300; U9];
+    ///
+    /// assert_eq!(is.binary_search(200), Ok(1));
+    /// assert_eq!(is.binary_search(250), Err(2));
+    /// ```
+    pub fn binary_search(&self, value: T::Value) -> Result<usize, usize> {
+        let index = self.lower_bound(value);
+        if index < self.len && self.get_unchecked(index) == value {
+            Ok(index)
+        } else {
+            Err(index)
         }
-        other.clear();
     }
 
     /// Returns the number of integers the vector can hold without reallocating.
@@ -146,7 +220,7 @@ impl<T: PackedInt> PackedIntegers<T> {
     /// assert_eq!(is.capacity(), 4);
     /// ```
     pub fn capacity(&self) -> usize {
-        self.buf.capacity() * Self::U32_NUM_BITS / T::NUM_BITS
+        self.buf.capacity() * W::NUM_BITS / T::NUM_BITS
     }
 
     /// Clears the vector. This method does not affect the vector's allocated capacity.
@@ -166,7 +240,225 @@ impl<T: PackedInt> PackedIntegers<T> {
         self.truncate(0)
     }
 
-    /// Creates an array of packed integers from a supplied `Vec<u32>` buffer.
+    /// Returns `true` if a sorted vector contains `value`, using a binary search.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let is = packed_ints![100, 200, 300; U9];
+    ///
+    /// assert!(is.contains_sorted(300));
+    /// assert!(!is.contains_sorted(400));
+    /// ```
+    pub fn contains_sorted(&self, value: T::Value) -> bool {
+        self.binary_search(value).is_ok()
+    }
+
+    /// Removes consecutive repeated integers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 100, 200, 300, 300, 300, 100; U9];
+    /// is.dedup();
+    ///
+    /// assert_eq!(is, packed_ints![100, 200, 300, 100; U9]);
+    /// ```
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b)
+    }
+
+    /// Removes all but the first of consecutive integers for which `same_bucket` returns `true`.
+    ///
+    /// `same_bucket` is passed each integer and the last integer kept before it, in that order.
+    /// Any change it makes to the kept integer is written back. Panics if a changed integer is
+    /// greater than `T::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![1, 2, 10, 11, 12, 30; U9];
+    /// is.dedup_by(|a, b| *a - *b < 5);
+    ///
+    /// assert_eq!(is, packed_ints![1, 10, 30; U9]);
+    /// ```
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T::Value, &mut T::Value) -> bool,
+    {
+        if self.len <= 1 {
+            return;
+        }
+
+        let mut last = self.get_unchecked(0);
+        let mut guard = RetainGuard {
+            vec: self,
+            processed: 1,
+            kept: 1,
+        };
+        while guard.processed < guard.vec.len {
+            let mut value = guard.vec.get_unchecked(guard.processed);
+            if !same_bucket(&mut value, &mut last) {
+                guard.vec.set_unchecked(guard.kept - 1, last);
+                guard.vec.set_unchecked(guard.kept, value);
+                last = value;
+                guard.kept += 1;
+            }
+            guard.processed += 1;
+        }
+        guard.vec.set_unchecked(guard.kept - 1, last);
+    }
+
+    /// Removes all but the first of consecutive integers that map to the same key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![10, 15, 20, 25, 32, 41; U9];
+    /// is.dedup_by_key(|v| *v / 10);
+    ///
+    /// assert_eq!(is, packed_ints![10, 20, 32, 41; U9]);
+    /// ```
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T::Value) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    /// Removes the integers in `range` from the vector, returning them as an iterator. Panics if
+    /// the range is out of bounds.
+    ///
+    /// Unlike `Vec::drain`, the integers are removed straight away, even if the iterator is not
+    /// consumed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300, 400, 500; U9];
+    /// let drained = is.drain(1..3);
+    ///
+    /// assert_eq!(drained.collect::<Vec<_>>(), vec![200, 300]);
+    /// assert_eq!(is, packed_ints![100, 400, 500; U9]);
+    /// ```
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> PackedIntegersIntoIterator<T, W> {
+        let (start, end) = to_bounds(range, self.len);
+        let drained = self.slice(start..end).to_packed();
+        self.shift_tail(end, start);
+
+        drained.into_iter()
+    }
+
+    /// Appends a copy of every integer of `other` to the back of the vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200; U9];
+    /// is.extend_from_packed(&packed_ints![300, 400; U9]);
+    ///
+    /// assert_eq!(is, packed_ints![100, 200, 300, 400; U9]);
+    /// ```
+    pub fn extend_from_packed(&mut self, other: &PackedIntegers<T, W>) {
+        raw::append_bits(
+            &mut self.buf,
+            self.len * T::NUM_BITS,
+            &other.buf,
+            0,
+            other.len * T::NUM_BITS,
+        );
+        self.len += other.len;
+    }
+
+    /// Appends every integer of `values` to the back of the vector. Panics if any value is
+    /// greater than `T::MAX`, keeping the integers before it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100; U9];
+    /// is.extend_from_slice(&[200, 300, 400]);
+    ///
+    /// assert_eq!(is, packed_ints![100, 200, 300, 400; U9]);
+    /// ```
+    pub fn extend_from_slice(&mut self, values: &[T::Value]) {
+        self.pack_iter(values.iter().copied());
+    }
+
+    /// Appends every integer of `iter` to the back of the vector, clamping any value greater than
+    /// `T::MAX` to `T::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U8};
+    ///
+    /// let mut is = packed_ints![10; U8];
+    /// is.extend_saturating(vec![20, 300, 40]);
+    ///
+    /// assert_eq!(is, packed_ints![10, 20, 255, 40; U8]);
+    /// ```
+    pub fn extend_saturating<I: IntoIterator<Item = T::Value>>(&mut self, iter: I) {
+        self.pack_iter(iter.into_iter().map(|value| value.min(T::MAX)));
+    }
+
+    /// Sets every integer in the vector to `value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300; U9];
+    /// is.fill(400);
+    ///
+    /// assert_eq!(is, packed_ints![400, 400, 400; U9]);
+    /// ```
+    pub fn fill(&mut self, value: T::Value) {
+        self.fill_range(.., value);
+    }
+
+    /// Sets every integer in `range` to `value`. Panics if the range is out of bounds, or `value`
+    /// is greater than `T::MAX`.
+    ///
+    /// Rather than setting each integer in turn, whole buffer cells are copied from a repeating
+    /// pattern.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300, 400; U9];
+    /// is.fill_range(1..3, 500);
+    ///
+    /// assert_eq!(is, packed_ints![100, 500, 500, 400; U9]);
+    /// ```
+    pub fn fill_range<R: RangeBounds<usize>>(&mut self, range: R, value: T::Value) {
+        let (start, end) = to_bounds(range, self.len);
+        assert_value::<T>(value);
+
+        let pattern = raw::fill_pattern::<W>(T::NUM_BITS, value.into());
+        raw::fill_bits(
+            &mut self.buf,
+            start * T::NUM_BITS,
+            end * T::NUM_BITS,
+            &pattern,
+        );
+    }
+
+    /// Creates an array of packed integers from a supplied `Vec<W>` buffer.
     /// Panics if `num_ints * T::NUM_BITS` is greater than the number of bits
     /// the buffer has.
     ///
@@ -184,24 +476,10 @@ impl<T: PackedInt> PackedIntegers<T> {
     /// assert_eq!(is.get(3), Some(0b0001));
     /// assert_eq!(is.get(4), None);
     /// ```
-    pub fn from_vec(buf: Vec<u32>, num_ints: usize) -> PackedIntegers<T> {
-        let total_bits = num_ints * T::NUM_BITS;
-        let mut min_len = total_bits / Self::U32_NUM_BITS;
-        if total_bits % Self::U32_NUM_BITS > 0 {
-            min_len += 1;
-        }
-        if buf.len() < min_len {
-            panic!(
-                "vector is too small (len {}, should be at least {})",
-                buf.len(),
-                min_len
-            );
-        }
-
-        PackedIntegers {
-            buf,
-            len: num_ints,
-            phantom: PhantomData,
+    pub fn from_vec(buf: Vec<W>, num_ints: usize) -> PackedIntegers<T, W> {
+        match Self::try_from_vec(buf, num_ints) {
+            Ok(is) => is,
+            Err(err) => panic!("{}", err),
         }
     }
 
@@ -217,7 +495,7 @@ impl<T: PackedInt> PackedIntegers<T> {
     /// assert_eq!(is.get(1), Some(200));
     /// assert_eq!(is.get(3), None);
     /// ```
-    pub fn get(&self, index: usize) -> Option<u32> {
+    pub fn get(&self, index: usize) -> Option<T::Value> {
         if index >= self.len {
             None
         } else {
@@ -225,20 +503,9 @@ impl<T: PackedInt> PackedIntegers<T> {
         }
     }
 
-    fn get_unchecked(&self, index: usize) -> u32 {
-        let buf_index = Self::buf_index(index);
-        let start_bit = Self::start_bit(index);
-        let available_bits = Self::available_bits(start_bit);
-
-        if available_bits >= T::NUM_BITS {
-            (self.buf[buf_index] >> start_bit) & T::MAX
-        } else {
-            // Value spans 2 buffer cells.
-            let lo = self.buf[buf_index] >> start_bit;
-            let hi = self.buf[buf_index + 1] << (Self::U32_NUM_BITS - start_bit);
-
-            lo ^ ((lo ^ hi) & (T::MAX >> available_bits << available_bits))
-        }
+    fn get_unchecked(&self, index: usize) -> T::Value {
+        let value = raw::read(&self.buf, index * T::NUM_BITS, T::NUM_BITS);
+        T::Value::from_u64(value)
     }
 
     /// Inserts an integer at position `index`, shifting all integers after it to the right.
@@ -256,21 +523,49 @@ impl<T: PackedInt> PackedIntegers<T> {
     /// is.insert(4, 50);
     /// assert_eq!(is, packed_ints![10, 40, 20, 30, 50; U8]);
     /// ```
-    pub fn insert(&mut self, index: usize, value: u32) {
+    pub fn insert(&mut self, index: usize, value: T::Value) {
         if index > self.len {
             panic!(
                 "insertion index (is {}) should be <= len (is {})",
                 index, self.len
             );
         }
+        assert_value::<T>(value);
 
-        self.push(value);
-        for i in ((index + 1)..self.len).rev() {
-            self.set_unchecked(i, self.get_unchecked(i - 1))
-        }
+        self.shift_tail(index, index + 1);
         self.set_unchecked(index, value);
     }
 
+    /// Inserts every integer of `values` at position `index`, shifting all integers after them to
+    /// the right. Panics if `index > len` or any value is greater than `T::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 400; U9];
+    /// is.insert_many(1, &[200, 300]);
+    ///
+    /// assert_eq!(is, packed_ints![100, 200, 300, 400; U9]);
+    /// ```
+    pub fn insert_many(&mut self, index: usize, values: &[T::Value]) {
+        if index > self.len {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                index, self.len
+            );
+        }
+        for &value in values {
+            assert_value::<T>(value);
+        }
+
+        self.shift_tail(index, index + values.len());
+        for (i, &value) in values.iter().enumerate() {
+            self.set_unchecked(index + i, value);
+        }
+    }
+
     /// Returns `true` if the vector contains no integers.
     ///
     /// # Example
@@ -288,6 +583,31 @@ impl<T: PackedInt> PackedIntegers<T> {
         self.len == 0
     }
 
+    /// Returns `true` if the integers are sorted in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// assert!(packed_ints![100, 100, 300; U9].is_sorted());
+    /// assert!(!packed_ints![100, 300, 200; U9].is_sorted());
+    /// ```
+    pub fn is_sorted(&self) -> bool {
+        let mut iter = self.iter();
+        let mut prev = match iter.next() {
+            Some(value) => value,
+            None => return true,
+        };
+        for value in iter {
+            if value < prev {
+                return false;
+            }
+            prev = value;
+        }
+        true
+    }
+
     /// Returns an iterator over the vector.
     ///
     /// # Example
@@ -303,7 +623,7 @@ impl<T: PackedInt> PackedIntegers<T> {
     /// assert_eq!(iter.next(), Some(511));
     /// assert_eq!(iter.next(), None);
     /// ```
-    pub fn iter(&self) -> PackedIntegersIterator<'_, T> {
+    pub fn iter(&self) -> PackedIntegersIterator<'_, T, W> {
         self.into_iter()
     }
 
@@ -322,6 +642,48 @@ impl<T: PackedInt> PackedIntegers<T> {
         self.len
     }
 
+    /// Returns the position of the first integer in a sorted vector that is not less than `value`,
+    /// or `len` if there is none.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let is = packed_ints![100, 200, 200, 300; U9];
+    ///
+    /// assert_eq!(is.lower_bound(200), 1);
+    /// assert_eq!(is.lower_bound(400), 4);
+    /// ```
+    pub fn lower_bound(&self, value: T::Value) -> usize {
+        self.partition_point(|&x| x < value)
+    }
+
+    /// Returns the position of the first integer for which `pred` returns `false`, given that
+    /// `pred` returns `true` for every integer before it and `false` for every integer after.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let is = packed_ints![100, 200, 300, 400; U9];
+    ///
+    /// assert_eq!(is.partition_point(|&v| v < 250), 2);
+    /// ```
+    pub fn partition_point<P: FnMut(&T::Value) -> bool>(&self, mut pred: P) -> usize {
+        let (mut low, mut high) = (0, self.len);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if pred(&self.get_unchecked(mid)) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
     /// Removes the last integer from the vector and returns it, or `None` if empty.
     ///
     /// # Example
@@ -334,7 +696,7 @@ impl<T: PackedInt> PackedIntegers<T> {
     /// assert_eq!(is.pop(), Some(300));
     /// assert_eq!(is, packed_ints![100, 200; U10]);
     /// ```
-    pub fn pop(&mut self) -> Option<u32> {
+    pub fn pop(&mut self) -> Option<T::Value> {
         if self.len == 0 {
             None
         } else {
@@ -355,36 +717,52 @@ impl<T: PackedInt> PackedIntegers<T> {
     ///
     /// assert_eq!(is, packed_ints![100, 200, 300; U10]);
     /// ```
-    pub fn push(&mut self, value: u32) {
-        if value > T::MAX {
-            panic!("value is outside the range 0..={}", T::MAX);
-        }
-
-        let buf_index = Self::buf_index(self.len);
-        let start_bit = Self::start_bit(self.len);
-        let available_bits = Self::available_bits(start_bit);
+    pub fn push(&mut self, value: T::Value) {
+        assert_value::<T>(value);
 
-        if available_bits >= T::NUM_BITS {
-            if buf_index == self.buf.len() {
-                self.buf.push(0);
-            }
-
-            self.buf[buf_index] &= !(T::MAX << start_bit);
-            self.buf[buf_index] |= value << start_bit;
-        } else {
-            // Value spans 2 buffer cells.
-            self.buf.push(0);
-
-            self.buf[buf_index] &= !(T::MAX << start_bit);
-            self.buf[buf_index] |= value << start_bit;
-
-            self.buf[buf_index + 1] &= !(T::MAX >> (Self::U32_NUM_BITS - start_bit));
-            self.buf[buf_index + 1] |= value >> available_bits;
+        let min_len = Self::to_buf_capacity(self.len + 1);
+        if self.buf.len() < min_len {
+            self.buf.resize(min_len, W::default());
         }
 
+        self.set_unchecked(self.len, value);
         self.len += 1;
     }
 
+    /// Appends an integer to the back of the vector, clamping `value` to `T::MAX` if it is
+    /// greater.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U10};
+    ///
+    /// let mut is = packed_ints![100, 200; U10];
+    /// is.push_saturating(3000);
+    ///
+    /// assert_eq!(is, packed_ints![100, 200, 1023; U10]);
+    /// ```
+    pub fn push_saturating(&mut self, value: T::Value) {
+        self.push(value.min(T::MAX));
+    }
+
+    /// Appends an integer to the back of the vector, keeping only the low `T::NUM_BITS` bits of
+    /// `value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U10};
+    ///
+    /// let mut is = packed_ints![100, 200; U10];
+    /// is.push_wrapping(1024 + 300);
+    ///
+    /// assert_eq!(is, packed_ints![100, 200, 300; U10]);
+    /// ```
+    pub fn push_wrapping(&mut self, value: T::Value) {
+        self.push(Self::wrap(value));
+    }
+
     /// Removes and returns the integer at position `index`, shifting all integers after it to the
     /// left.
     ///
@@ -398,7 +776,7 @@ impl<T: PackedInt> PackedIntegers<T> {
     /// assert_eq!(is.remove(1), 20);
     /// assert_eq!(is, packed_ints![10, 30; U8]);
     /// ```
-    pub fn remove(&mut self, index: usize) -> u32 {
+    pub fn remove(&mut self, index: usize) -> T::Value {
         if index >= self.len {
             panic!(
                 "removal index (is {}) should be < len (is {})",
@@ -407,13 +785,59 @@ impl<T: PackedInt> PackedIntegers<T> {
         }
 
         let result = self.get_unchecked(index);
+        self.shift_tail(index + 1, index);
+
+        result
+    }
+
+    /// Copies the integers into a vector of a different width. Widening always succeeds, while
+    /// narrowing fails with the position and value of the first integer that is greater than
+    /// `U::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U7, U12, U17};
+    ///
+    /// let is = packed_ints![100, 2000, 4000; U12];
+    ///
+    /// let wide = is.repack::<U17>().unwrap();
+    /// assert_eq!(wide, packed_ints![100, 2000, 4000; U17]);
+    ///
+    /// let err = is.repack::<U7>().unwrap_err();
+    /// assert_eq!(err.index, 1);
+    /// assert_eq!(err.value, 2000);
+    /// ```
+    pub fn repack<U: PackedInt>(&self) -> Result<PackedIntegers<U, W>, RepackError> {
+        let buf_len = raw::buf_len::<W>(self.len, U::NUM_BITS);
+        if U::NUM_BITS == T::NUM_BITS {
+            return Ok(PackedIntegers {
+                buf: self.buf[..buf_len].to_vec(),
+                len: self.len,
+                phantom: PhantomData,
+            });
+        }
 
-        for i in (index + 1)..self.len {
-            self.set_unchecked(i - 1, self.get_unchecked(i));
+        // Validate before allocating, so that a failed narrowing does not pay for the copy.
+        if U::NUM_BITS < T::NUM_BITS {
+            let max: u64 = U::MAX.into();
+            let mut values = self.iter().map(Into::<u64>::into).enumerate();
+            if let Some((index, value)) = values.find(|&(_, value)| value > max) {
+                return Err(RepackError { index, value, max });
+            }
         }
-        self.len -= 1;
 
-        result
+        let mut buf = vec![W::default(); buf_len];
+        for index in 0..self.len {
+            let value = raw::read(&self.buf, index * T::NUM_BITS, T::NUM_BITS);
+            raw::write(&mut buf, index * U::NUM_BITS, U::NUM_BITS, value);
+        }
+
+        Ok(PackedIntegers {
+            buf,
+            len: self.len,
+            phantom: PhantomData,
+        })
     }
 
     /// Reserves capacity for _at least_ `additional` more integers to be inserted.
@@ -436,6 +860,64 @@ impl<T: PackedInt> PackedIntegers<T> {
         self.buf.reserve(additional);
     }
 
+    /// Resizes the vector to `new_len` integers, filling any new integers with `value`. Panics if
+    /// `value` is greater than `T::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100; U9];
+    ///
+    /// is.resize(3, 200);
+    /// assert_eq!(is, packed_ints![100, 200, 200; U9]);
+    ///
+    /// is.resize(2, 300);
+    /// assert_eq!(is, packed_ints![100, 200; U9]);
+    /// ```
+    pub fn resize(&mut self, new_len: usize, value: T::Value) {
+        if new_len <= self.len {
+            self.truncate(new_len);
+            return;
+        }
+        assert_value::<T>(value);
+
+        let min_len = Self::to_buf_capacity(new_len);
+        if self.buf.len() < min_len {
+            self.buf.resize(min_len, W::default());
+        }
+        let old_len = self.len;
+        self.len = new_len;
+        self.fill_range(old_len.., value);
+    }
+
+    /// Resizes the vector to `new_len` integers, filling any new integers with the values
+    /// returned by `f`. Panics if any value is greater than `T::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100; U9];
+    /// let mut next = 100;
+    /// is.resize_with(4, || {
+    ///     next += 100;
+    ///     next
+    /// });
+    ///
+    /// assert_eq!(is, packed_ints![100, 200, 300, 400; U9]);
+    /// ```
+    pub fn resize_with<F: FnMut() -> T::Value>(&mut self, new_len: usize, f: F) {
+        if new_len <= self.len {
+            self.truncate(new_len);
+        } else {
+            let additional = new_len - self.len;
+            self.pack_iter(std::iter::repeat_with(f).take(additional));
+        }
+    }
+
     /// Sets the integer value at `index` to `value`.
     ///
     /// # Example
@@ -448,40 +930,368 @@ impl<T: PackedInt> PackedIntegers<T> {
     ///
     /// assert_eq!(is, packed_ints![100, 400, 300; U9]);
     /// ```
-    pub fn set(&mut self, index: usize, value: u32) {
+    pub fn set(&mut self, index: usize, value: T::Value) {
         if index >= self.len {
             panic!(
                 "index out of bounds: the len is {} but the index is {}",
                 self.len, index
             );
-        } else {
-            self.set_unchecked(index, value);
         }
+
+        self.set_unchecked(index, value);
+    }
+
+    fn set_unchecked(&mut self, index: usize, value: T::Value) {
+        assert_value::<T>(value);
+
+        raw::write(
+            &mut self.buf,
+            index * T::NUM_BITS,
+            T::NUM_BITS,
+            value.into(),
+        );
+    }
+
+    /// Sets the integer value at `index` to `value`, clamping `value` to `T::MAX` if it is
+    /// greater.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300; U9];
+    /// is.set_saturating(1, 600);
+    ///
+    /// assert_eq!(is, packed_ints![100, 511, 300; U9]);
+    /// ```
+    pub fn set_saturating(&mut self, index: usize, value: T::Value) {
+        self.set(index, value.min(T::MAX));
+    }
+
+    /// Sets the integer value at `index` to `value`, keeping only the low `T::NUM_BITS` bits of
+    /// `value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300; U9];
+    /// is.set_wrapping(1, 600);
+    ///
+    /// assert_eq!(is, packed_ints![100, 88, 300; U9]);
+    /// ```
+    pub fn set_wrapping(&mut self, index: usize, value: T::Value) {
+        self.set(index, Self::wrap(value));
+    }
+
+    /// Keeps only the integers for which `f` returns `true`, preserving their order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300, 400, 500; U9];
+    /// is.retain(|&v| v % 200 != 0);
+    ///
+    /// assert_eq!(is, packed_ints![100, 300, 500; U9]);
+    /// ```
+    pub fn retain<F: FnMut(&T::Value) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|value| f(value))
     }
 
-    fn set_unchecked(&mut self, index: usize, value: u32) {
-        if value > T::MAX {
-            panic!("value is outside the range 0..={}", T::MAX);
+    /// Keeps only the integers for which `f` returns `true`, preserving their order. Any change
+    /// `f` makes to a kept integer is written back. Panics if a changed integer is greater than
+    /// `T::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300, 400, 500; U9];
+    /// is.retain_mut(|v| {
+    ///     *v += 1;
+    ///     *v < 400
+    /// });
+    ///
+    /// assert_eq!(is, packed_ints![101, 201, 301; U9]);
+    /// ```
+    pub fn retain_mut<F: FnMut(&mut T::Value) -> bool>(&mut self, mut f: F) {
+        let mut guard = RetainGuard {
+            vec: self,
+            processed: 0,
+            kept: 0,
+        };
+        while guard.processed < guard.vec.len {
+            let mut value = guard.vec.get_unchecked(guard.processed);
+            if f(&mut value) {
+                guard.vec.set_unchecked(guard.kept, value);
+                guard.kept += 1;
+            }
+            guard.processed += 1;
         }
+    }
 
-        let buf_index = Self::buf_index(index);
-        let start_bit = Self::start_bit(index);
-        let available_bits = Self::available_bits(start_bit);
+    /// Shrinks the capacity of the vector with a lower bound.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U8};
+    ///
+    /// let mut is = packed_ints![1, 2, 3, 4, 5, 6, 7, 8, 9; U8];
+    /// is.truncate(2);
+    /// is.shrink_to(6);
+    ///
+    /// assert!(is.capacity() >= 6);
+    /// assert!(is.capacity() < 9);
+    /// ```
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.buf.truncate(Self::to_buf_capacity(self.len));
+        self.buf
+            .shrink_to(Self::to_buf_capacity(min_capacity.max(self.len)));
+    }
 
-        if available_bits >= T::NUM_BITS {
-            self.buf[buf_index] &= !(T::MAX << start_bit);
-            self.buf[buf_index] |= value << start_bit;
-        } else {
-            // Value spans 2 buffer cells.
-            self.buf[buf_index] &= !(T::MAX << start_bit);
-            self.buf[buf_index] |= value << start_bit;
+    /// Shrinks the capacity of the vector as much as possible.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U8};
+    ///
+    /// let mut is = packed_ints![1, 2, 3, 4, 5, 6, 7, 8, 9; U8];
+    /// is.truncate(2);
+    /// is.shrink_to_fit();
+    ///
+    /// assert_eq!(is.capacity(), 4);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.buf.truncate(Self::to_buf_capacity(self.len));
+        self.buf.shrink_to_fit();
+    }
+
+    /// Returns a read-only slice of the integers in `range`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let is = packed_ints![100, 200, 300, 400, 500; U9];
+    /// let slice = is.slice(1..4);
+    ///
+    /// assert_eq!(slice.len(), 3);
+    /// assert_eq!(slice.get(0), Some(200));
+    /// assert_eq!(slice.slice(1..).to_packed(), packed_ints![300, 400; U9]);
+    /// ```
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> PackedSlice<'_, T, W> {
+        let (start, end) = to_bounds(range, self.len);
+        PackedSlice::new(&self.buf, start * T::NUM_BITS, end - start)
+    }
+
+    /// Returns a mutable slice of the integers in `range`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300, 400, 500; U9];
+    /// let mut slice = is.slice_mut(1..4);
+    /// slice.set(0, 0);
+    /// slice.swap(1, 2);
+    ///
+    /// assert_eq!(is, packed_ints![100, 0, 400, 300, 500; U9]);
+    /// ```
+    pub fn slice_mut<R: RangeBounds<usize>>(&mut self, range: R) -> PackedSliceMut<'_, T, W> {
+        let (start, end) = to_bounds(range, self.len);
+        PackedSliceMut::new(&mut self.buf, start * T::NUM_BITS, end - start)
+    }
+
+    /// Divides the integers into two mutable slices at position `mid`. Panics if `mid > len`.
+    ///
+    /// The two slices can be written independently, even where they share a buffer cell.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300, 400; U9];
+    /// let (mut lo, mut hi) = is.split_at_mut(3);
+    /// lo.fill(1);
+    /// hi.fill(2);
+    ///
+    /// assert_eq!(is, packed_ints![1, 1, 1, 2; U9]);
+    /// ```
+    pub fn split_at_mut(
+        &mut self,
+        mid: usize,
+    ) -> (PackedSliceMut<'_, T, W>, PackedSliceMut<'_, T, W>) {
+        self.slice_mut(..).split(mid)
+    }
+
+    /// Sorts the integers in ascending order.
+    ///
+    /// Equal integers are indistinguishable, so this is the same as `sort_unstable`, and uses no
+    /// extra memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![300, 100, 200; U9];
+    /// is.sort();
+    ///
+    /// assert_eq!(is, packed_ints![100, 200, 300; U9]);
+    /// ```
+    pub fn sort(&mut self) {
+        self.sort_unstable();
+    }
+
+    /// Sorts the integers by the key `f` returns for each, keeping integers with equal keys in
+    /// their original order.
+    ///
+    /// This is a merge sort, which needs a scratch buffer the size of the vector's buffer. If `f`
+    /// panics, the vector keeps all of its integers, in an unspecified order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![13, 21, 11, 22, 12; U9];
+    /// is.sort_by_key(|&v| v % 10);
+    ///
+    /// assert_eq!(is, packed_ints![21, 11, 22, 12, 13; U9]);
+    /// ```
+    pub fn sort_by_key<K: Ord, F: FnMut(&T::Value) -> K>(&mut self, mut f: F) {
+        sort::merge_sort(&mut self.buf, T::NUM_BITS, self.len, |value| {
+            f(&T::Value::from_u64(value))
+        });
+    }
+
+    /// Sorts the integers in ascending order, without allocating.
+    ///
+    /// This is an in-place MSD radix sort on the packed buffer, making at most one pass for each
+    /// 8 bits of `T::NUM_BITS`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![300, 100, 511, 0, 200; U9];
+    /// is.sort_unstable();
+    ///
+    /// assert_eq!(is, packed_ints![0, 100, 200, 300, 511; U9]);
+    /// ```
+    pub fn sort_unstable(&mut self) {
+        sort::radix_sort(&mut self.buf, T::NUM_BITS, 0, self.len, T::NUM_BITS);
+    }
+
+    /// Replaces the integers in `range` with those of `replace_with`, returning the removed
+    /// integers as an iterator. Panics if the range is out of bounds, or any new value is greater
+    /// than `T::MAX`.
+    ///
+    /// Like `drain`, the integers are replaced straight away, even if the iterator is not
+    /// consumed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300, 400; U9];
+    /// let removed = is.splice(1..3, vec![500, 501, 502]);
+    ///
+    /// assert_eq!(removed.collect::<Vec<_>>(), vec![200, 300]);
+    /// assert_eq!(is, packed_ints![100, 500, 501, 502, 400; U9]);
+    /// ```
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> PackedIntegersIntoIterator<T, W>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T::Value>,
+    {
+        let (start, end) = to_bounds(range, self.len);
+        let replace_with: PackedIntegers<T, W> = replace_with.into_iter().collect();
+        let removed = self.slice(start..end).to_packed();
+
+        self.shift_tail(end, start + replace_with.len);
+        for (i, value) in replace_with.iter().enumerate() {
+            self.set_unchecked(start + i, value);
+        }
+
+        removed.into_iter()
+    }
 
-            self.buf[buf_index + 1] = !(T::MAX >> (Self::U32_NUM_BITS - start_bit));
-            self.buf[buf_index + 1] |= value >> available_bits;
+    /// Splits the vector in two at position `at`, returning the integers from `at` onwards.
+    /// Panics if `at > len`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300, 400; U9];
+    /// let tail = is.split_off(1);
+    ///
+    /// assert_eq!(is, packed_ints![100; U9]);
+    /// assert_eq!(tail, packed_ints![200, 300, 400; U9]);
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> PackedIntegers<T, W> {
+        if at > self.len {
+            panic!(
+                "`at` split index (is {}) should be <= len (is {})",
+                at, self.len
+            );
         }
+
+        let mut buf = Vec::new();
+        raw::append_bits(
+            &mut buf,
+            0,
+            &self.buf,
+            at * T::NUM_BITS,
+            (self.len - at) * T::NUM_BITS,
+        );
+        let other = PackedIntegers::from_vec(buf, self.len - at);
+        self.len = at;
+        other
     }
 
-    /// Returns a copy of the backing `Vec<u32>` buffer.
+    /// Removes and returns the integer at position `index`, replacing it with the last integer.
+    /// Panics if `index >= len`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300, 400; U9];
+    ///
+    /// assert_eq!(is.swap_remove(1), 200);
+    /// assert_eq!(is, packed_ints![100, 400, 300; U9]);
+    /// ```
+    pub fn swap_remove(&mut self, index: usize) -> T::Value {
+        if index >= self.len {
+            panic!(
+                "swap_remove index (is {}) should be < len (is {})",
+                index, self.len
+            );
+        }
+
+        let result = self.get_unchecked(index);
+        self.len -= 1;
+        self.set_unchecked(index, self.get_unchecked(self.len));
+        result
+    }
+
+    /// Returns a copy of the backing `Vec<W>` buffer.
     ///
     /// # Example
     ///
@@ -499,22 +1309,8 @@ impl<T: PackedInt> PackedIntegers<T> {
     /// assert_eq!(vec.len(), 1);
     /// assert_eq!(vec[0], 0b00001000_00000100_00000010_00000001);
     /// ```
-    pub fn to_vec(&self) -> Vec<u32> {
-        let buf_index = Self::buf_index(self.len);
-        let start_bit = Self::start_bit(self.len);
-
-        // Certain functions "remove" integers by setting the struct's `len` value. A side effect
-        // of this optimisation is that the actual contents of the backing vector may not be what
-        // is expected. Ensure that any lingering integers are cleaned up prior to returning this
-        // copy.
-        let mut vec;
-        if start_bit > 0 {
-            vec = self.buf[0..=buf_index].to_vec();
-            vec[buf_index] &= !(u32::MAX << start_bit);
-        } else {
-            vec = self.buf[0..buf_index].to_vec();
-        }
-        vec
+    pub fn to_vec(&self) -> Vec<W> {
+        raw::to_vec(&self.buf, self.len * T::NUM_BITS)
     }
 
     /// Keeps the first `len` integers, and drops the rest.
@@ -536,94 +1332,602 @@ impl<T: PackedInt> PackedIntegers<T> {
         self.len = len;
     }
 
-    #[inline]
-    fn available_bits(start_bit: usize) -> usize {
-        Self::U32_NUM_BITS - start_bit
+    /// Creates an array of packed integers from a supplied `Vec<W>` buffer, or returns an error
+    /// if `num_ints * T::NUM_BITS` is greater than the number of bits the buffer has.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedIntegers, PackedIntegersError, U8};
+    ///
+    /// let is = PackedIntegers::<U8>::try_from_vec(vec![0x04030201], 4).unwrap();
+    /// assert_eq!(is.get(3), Some(4));
+    ///
+    /// let err = PackedIntegers::<U8>::try_from_vec(vec![0x04030201], 5).unwrap_err();
+    /// assert_eq!(err, PackedIntegersError::BufferTooSmall { len: 1, required: 2 });
+    /// ```
+    pub fn try_from_vec(
+        buf: Vec<W>,
+        num_ints: usize,
+    ) -> Result<PackedIntegers<T, W>, PackedIntegersError> {
+        let required = Self::to_buf_capacity(num_ints);
+        if buf.len() < required {
+            return Err(PackedIntegersError::BufferTooSmall {
+                len: buf.len(),
+                required,
+            });
+        }
+
+        Ok(PackedIntegers {
+            buf,
+            len: num_ints,
+            phantom: PhantomData,
+        })
     }
 
-    #[inline]
-    fn buf_index(index: usize) -> usize {
-        index * T::NUM_BITS / Self::U32_NUM_BITS
+    /// Inserts an integer at position `index`, shifting all integers after it to the right, or
+    /// returns an error if `index > len` or `value` is greater than `T::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, PackedIntegersError, U8};
+    ///
+    /// let mut is = packed_ints![10, 20, 30; U8];
+    ///
+    /// assert_eq!(is.try_insert(1, 40), Ok(()));
+    /// assert_eq!(
+    ///     is.try_insert(5, 50),
+    ///     Err(PackedIntegersError::IndexOutOfBounds { index: 5, len: 4 })
+    /// );
+    /// assert_eq!(is, packed_ints![10, 40, 20, 30; U8]);
+    /// ```
+    pub fn try_insert(&mut self, index: usize, value: T::Value) -> Result<(), PackedIntegersError> {
+        if index > self.len {
+            return Err(PackedIntegersError::IndexOutOfBounds {
+                index,
+                len: self.len,
+            });
+        }
+
+        check_value::<T>(value)?;
+        self.insert(index, value);
+        Ok(())
     }
 
-    #[inline]
-    fn start_bit(index: usize) -> usize {
-        index * T::NUM_BITS % Self::U32_NUM_BITS
+    /// Appends an integer to the back of the vector, or returns an error if `value` is greater
+    /// than `T::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, PackedIntegersError, U10};
+    ///
+    /// let mut is = packed_ints![100, 200; U10];
+    ///
+    /// assert_eq!(is.try_push(300), Ok(()));
+    /// assert_eq!(
+    ///     is.try_push(3000),
+    ///     Err(PackedIntegersError::ValueOutOfRange { value: 3000, max: 1023 })
+    /// );
+    /// assert_eq!(is, packed_ints![100, 200, 300; U10]);
+    /// ```
+    pub fn try_push(&mut self, value: T::Value) -> Result<(), PackedIntegersError> {
+        check_value::<T>(value)?;
+        self.push(value);
+        Ok(())
+    }
+
+    /// Removes and returns the integer at position `index`, shifting all integers after it to the
+    /// left, or returns an error if `index >= len`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, PackedIntegersError, U8};
+    ///
+    /// let mut is = packed_ints![10, 20, 30; U8];
+    ///
+    /// assert_eq!(is.try_remove(1), Ok(20));
+    /// assert_eq!(
+    ///     is.try_remove(2),
+    ///     Err(PackedIntegersError::IndexOutOfBounds { index: 2, len: 2 })
+    /// );
+    /// ```
+    pub fn try_remove(&mut self, index: usize) -> Result<T::Value, PackedIntegersError> {
+        if index >= self.len {
+            return Err(PackedIntegersError::IndexOutOfBounds {
+                index,
+                len: self.len,
+            });
+        }
+
+        Ok(self.remove(index))
+    }
+
+    /// Sets the integer value at `index` to `value`, or returns an error if `index >= len` or
+    /// `value` is greater than `T::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, PackedIntegersError, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300; U9];
+    ///
+    /// assert_eq!(is.try_set(1, 400), Ok(()));
+    /// assert_eq!(
+    ///     is.try_set(1, 600),
+    ///     Err(PackedIntegersError::ValueOutOfRange { value: 600, max: 511 })
+    /// );
+    /// assert_eq!(is, packed_ints![100, 400, 300; U9]);
+    /// ```
+    pub fn try_set(&mut self, index: usize, value: T::Value) -> Result<(), PackedIntegersError> {
+        if index >= self.len {
+            return Err(PackedIntegersError::IndexOutOfBounds {
+                index,
+                len: self.len,
+            });
+        }
+
+        check_value::<T>(value)?;
+        self.set(index, value);
+        Ok(())
+    }
+
+    /// Returns the position of the first integer in a sorted vector that is greater than `value`,
+    /// or `len` if there is none.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let is = packed_ints![100, 200, 200, 300; U9];
+    ///
+    /// assert_eq!(is.upper_bound(200), 3);
+    /// assert_eq!(is.upper_bound(50), 0);
+    /// ```
+    pub fn upper_bound(&self, value: T::Value) -> usize {
+        self.partition_point(|&x| x <= value)
+    }
+
+    /// Appends every integer of `iter`, packing them into whole cells rather than writing each one
+    /// in place. Panics if any value is greater than `T::MAX`, keeping the integers before it.
+    fn pack_iter<I: Iterator<Item = T::Value>>(&mut self, iter: I) {
+        self.reserve(iter.size_hint().0);
+
+        // Take the partially filled last cell, if any, as the start of the accumulator. Any bits
+        // past the end of the vector are dropped.
+        self.buf.truncate(Self::to_buf_capacity(self.len));
+        let mut acc_bits = self.len * T::NUM_BITS % W::NUM_BITS;
+        let mut acc = match self.buf.pop() {
+            Some(cell) if acc_bits > 0 => u128::from(cell.into() & raw::max(acc_bits)),
+            Some(cell) => {
+                self.buf.push(cell);
+                0
+            }
+            None => 0,
+        };
+
+        let mut overflow = None;
+        for value in iter {
+            if value > T::MAX {
+                overflow = Some(value);
+                break;
+            }
+
+            acc |= u128::from(value.into()) << acc_bits;
+            acc_bits += T::NUM_BITS;
+            while acc_bits >= W::NUM_BITS {
+                self.buf.push(W::from_u64(acc as u64));
+                acc >>= W::NUM_BITS;
+                acc_bits -= W::NUM_BITS;
+            }
+            self.len += 1;
+        }
+        if acc_bits > 0 {
+            self.buf.push(W::from_u64(acc as u64));
+        }
+
+        if let Some(value) = overflow {
+            assert_value::<T>(value);
+        }
+    }
+
+    /// Moves the integers from position `from` onwards to start at position `to`, growing the
+    /// buffer if needed, and updates the length to match.
+    fn shift_tail(&mut self, from: usize, to: usize) {
+        let tail_len = self.len - from;
+        let new_len = to + tail_len;
+        let min_len = Self::to_buf_capacity(new_len);
+        if self.buf.len() < min_len {
+            self.buf.resize(min_len, W::default());
+        }
+
+        raw::copy_bits_within(
+            &mut self.buf,
+            from * T::NUM_BITS,
+            to * T::NUM_BITS,
+            tail_len * T::NUM_BITS,
+        );
+        self.len = new_len;
     }
 
     #[inline]
     fn to_buf_capacity(capacity: usize) -> usize {
-        (T::NUM_BITS * capacity + (Self::U32_NUM_BITS - 1)) / Self::U32_NUM_BITS
+        raw::buf_len::<W>(capacity, T::NUM_BITS)
+    }
+
+    #[inline]
+    fn wrap(value: T::Value) -> T::Value {
+        T::Value::from_u64(value.into() & T::MAX.into())
+    }
+}
+
+/// Compacts a vector part way through `retain_mut` or `dedup_by` when dropped. Integers before
+/// `processed` have been visited and the first `kept` of them are kept; the rest are moved down
+/// behind them, so the vector stays consistent even if the closure panics.
+struct RetainGuard<'a, T: PackedInt, W: Word> {
+    vec: &'a mut PackedIntegers<T, W>,
+    processed: usize,
+    kept: usize,
+}
+
+impl<T: PackedInt, W: Word> Drop for RetainGuard<'_, T, W> {
+    fn drop(&mut self) {
+        self.vec.shift_tail(self.processed, self.kept);
     }
 }
 
 /// A consuming iterator for `PackedIntegers`.
-pub struct PackedIntegersIntoIterator<T: PackedInt> {
-    vec: PackedIntegers<T>,
+///
+/// Like `PackedIntegersIterator`, it streams through the buffer when moving forwards.
+pub struct PackedIntegersIntoIterator<T: PackedInt, W: Word = u32> {
+    vec: PackedIntegers<T, W>,
     index: usize,
+    end: usize,
+    decoder: Decoder<T, W>,
+}
+
+impl<T: PackedInt, W: Word> PackedIntegersIntoIterator<T, W> {
+    /// Decodes integers into `out` until it is full or the iterator is exhausted, returning the
+    /// number of integers decoded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedIntegers, U9};
+    ///
+    /// let is: PackedIntegers<U9> = (0..10).collect();
+    /// let mut iter = is.into_iter();
+    /// let mut out = [0; 4];
+    ///
+    /// assert_eq!(iter.decode_into(&mut out), 4);
+    /// assert_eq!(out, [0, 1, 2, 3]);
+    /// assert_eq!(iter.decode_into(&mut out), 4);
+    /// assert_eq!(iter.decode_into(&mut out), 2);
+    /// assert_eq!(out[..2], [8, 9]);
+    /// ```
+    pub fn decode_into(&mut self, out: &mut [T::Value]) -> usize {
+        let count = out.len().min(self.end - self.index);
+
+        for value in &mut out[..count] {
+            *value = self.decoder.decode(&self.vec.buf);
+        }
+        self.index += count;
+        count
+    }
+
+    /// Returns the next `N` integers as an array, or `None` if fewer than `N` remain. The
+    /// iterator is not advanced in that case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedIntegers, U9};
+    ///
+    /// let is: PackedIntegers<U9> = (0..5).collect();
+    /// let mut iter = is.into_iter();
+    ///
+    /// assert_eq!(iter.next_chunk::<2>(), Some([0, 1]));
+    /// assert_eq!(iter.next_chunk::<2>(), Some([2, 3]));
+    /// assert_eq!(iter.next_chunk::<2>(), None);
+    /// assert_eq!(iter.next(), Some(4));
+    /// ```
+    pub fn next_chunk<const N: usize>(&mut self) -> Option<[T::Value; N]> {
+        if self.end - self.index < N {
+            return None;
+        }
+
+        let mut chunk = [T::Value::from_u64(0); N];
+        self.decode_into(&mut chunk);
+        Some(chunk)
+    }
+
+    /// Moves the front of the iterator to `index`, reloading the bit buffer.
+    fn seek(&mut self, index: usize) {
+        self.index = index;
+        self.decoder
+            .seek(&self.vec.buf, index * T::NUM_BITS, index < self.end);
+    }
 }
 
-impl<T: PackedInt> IntoIterator for PackedIntegers<T> {
-    type Item = u32;
-    type IntoIter = PackedIntegersIntoIterator<T>;
+impl<T: PackedInt, W: Word> IntoIterator for PackedIntegers<T, W> {
+    type Item = T::Value;
+    type IntoIter = PackedIntegersIntoIterator<T, W>;
 
     fn into_iter(self) -> Self::IntoIter {
-        PackedIntegersIntoIterator {
+        let mut iter = PackedIntegersIntoIterator {
+            end: self.len,
             vec: self,
             index: 0,
-        }
+            decoder: Decoder::new(),
+        };
+        iter.seek(0);
+        iter
     }
 }
 
-impl<T: PackedInt> Iterator for PackedIntegersIntoIterator<T> {
-    type Item = u32;
+impl<T: PackedInt, W: Word> Iterator for PackedIntegersIntoIterator<T, W> {
+    type Item = T::Value;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let result = self.vec.get(self.index);
+        if self.index >= self.end {
+            return None;
+        }
+
+        let result = self.decoder.decode(&self.vec.buf);
         self.index += 1;
 
-        result
+        Some(result)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n > 0 {
+            self.seek(self.index.saturating_add(n).min(self.end));
+        }
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<T: PackedInt, W: Word> DoubleEndedIterator for PackedIntegersIntoIterator<T, W> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        Some(self.vec.get_unchecked(self.end))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.end = self.end.saturating_sub(n).max(self.index);
+        self.next_back()
     }
 }
 
+impl<T: PackedInt, W: Word> ExactSizeIterator for PackedIntegersIntoIterator<T, W> {}
+
+impl<T: PackedInt, W: Word> FusedIterator for PackedIntegersIntoIterator<T, W> {}
+
 /// An iterator for `PackedIntegers`.
-pub struct PackedIntegersIterator<'a, T: PackedInt> {
-    vec: &'a PackedIntegers<T>,
+///
+/// Moving forwards, the iterator streams through the buffer one cell at a time, keeping the bits
+/// it has loaded but not yet returned in a bit buffer. Each integer is then decoded with shifts
+/// alone.
+pub struct PackedIntegersIterator<'a, T: PackedInt, W: Word = u32> {
+    buf: &'a [W],
+    offset: usize,
     index: usize,
+    end: usize,
+    decoder: Decoder<T, W>,
 }
 
-impl<'a, T: PackedInt> IntoIterator for &'a PackedIntegers<T> {
-    type Item = u32;
-    type IntoIter = PackedIntegersIterator<'a, T>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        PackedIntegersIterator {
-            vec: self,
+impl<'a, T: PackedInt, W: Word> PackedIntegersIterator<'a, T, W> {
+    fn new(buf: &'a [W], offset: usize, len: usize) -> PackedIntegersIterator<'a, T, W> {
+        let mut iter = PackedIntegersIterator {
+            buf,
+            offset,
             index: 0,
+            end: len,
+            decoder: Decoder::new(),
+        };
+        iter.seek(0);
+        iter
+    }
+
+    /// Decodes integers into `out` until it is full or the iterator is exhausted, returning the
+    /// number of integers decoded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedIntegers, U9};
+    ///
+    /// let is: PackedIntegers<U9> = (0..10).collect();
+    /// let mut iter = is.iter();
+    /// let mut out = [0; 4];
+    ///
+    /// assert_eq!(iter.decode_into(&mut out), 4);
+    /// assert_eq!(out, [0, 1, 2, 3]);
+    /// assert_eq!(iter.decode_into(&mut out), 4);
+    /// assert_eq!(iter.decode_into(&mut out), 2);
+    /// assert_eq!(out[..2], [8, 9]);
+    /// ```
+    pub fn decode_into(&mut self, out: &mut [T::Value]) -> usize {
+        let count = out.len().min(self.end - self.index);
+
+        for value in &mut out[..count] {
+            *value = self.decoder.decode(self.buf);
         }
+        self.index += count;
+        count
+    }
+
+    /// Returns the next `N` integers as an array, or `None` if fewer than `N` remain. The
+    /// iterator is not advanced in that case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedIntegers, U9};
+    ///
+    /// let is: PackedIntegers<U9> = (0..5).collect();
+    /// let mut iter = is.iter();
+    ///
+    /// assert_eq!(iter.next_chunk::<2>(), Some([0, 1]));
+    /// assert_eq!(iter.next_chunk::<2>(), Some([2, 3]));
+    /// assert_eq!(iter.next_chunk::<2>(), None);
+    /// assert_eq!(iter.next(), Some(4));
+    /// ```
+    pub fn next_chunk<const N: usize>(&mut self) -> Option<[T::Value; N]> {
+        if self.end - self.index < N {
+            return None;
+        }
+
+        let mut chunk = [T::Value::from_u64(0); N];
+        self.decode_into(&mut chunk);
+        Some(chunk)
+    }
+
+    #[inline]
+    fn read(&self, index: usize) -> T::Value {
+        let bit = self.offset + index * T::NUM_BITS;
+        T::Value::from_u64(raw::read(self.buf, bit, T::NUM_BITS))
+    }
+
+    /// Moves the front of the iterator to `index`, reloading the bit buffer.
+    fn seek(&mut self, index: usize) {
+        self.index = index;
+        self.decoder.seek(
+            self.buf,
+            self.offset + index * T::NUM_BITS,
+            index < self.end,
+        );
+    }
+}
+
+impl<'a, T: PackedInt, W: Word> IntoIterator for &'a PackedIntegers<T, W> {
+    type Item = T::Value;
+    type IntoIter = PackedIntegersIterator<'a, T, W>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        PackedIntegersIterator::new(&self.buf, 0, self.len)
     }
 }
 
-impl<'a, T: PackedInt> Iterator for PackedIntegersIterator<'a, T> {
-    type Item = u32;
+impl<'a, T: PackedInt, W: Word> Iterator for PackedIntegersIterator<'a, T, W> {
+    type Item = T::Value;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let result = self.vec.get(self.index);
+        if self.index >= self.end {
+            return None;
+        }
+
+        let result = self.decoder.decode(self.buf);
         self.index += 1;
 
-        result
+        Some(result)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n > 0 {
+            self.seek(self.index.saturating_add(n).min(self.end));
+        }
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<T: PackedInt, W: Word> DoubleEndedIterator for PackedIntegersIterator<'_, T, W> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        Some(self.read(self.end))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.end = self.end.saturating_sub(n).max(self.index);
+        self.next_back()
     }
 }
 
-impl<T: PackedInt> Eq for PackedIntegers<T> {}
+impl<T: PackedInt, W: Word> ExactSizeIterator for PackedIntegersIterator<'_, T, W> {}
+
+impl<T: PackedInt, W: Word> FusedIterator for PackedIntegersIterator<'_, T, W> {}
+
+/// The bit buffer behind the iterators' forward decoding. It holds the `num_bits` bits following
+/// the next integer, and `cell` is the next buffer cell to load.
+struct Decoder<T: PackedInt, W: Word> {
+    bits: u128,
+    num_bits: usize,
+    cell: usize,
+    phantom: PhantomData<(T, W)>,
+}
 
-impl<T: PackedInt> PartialEq for PackedIntegers<T> {
+impl<T: PackedInt, W: Word> Decoder<T, W> {
+    fn new() -> Decoder<T, W> {
+        Decoder {
+            bits: 0,
+            num_bits: 0,
+            cell: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Decodes the next integer, refilling the bit buffer from `buf` as needed. The caller must
+    /// ensure there is an integer left to decode.
+    #[inline]
+    fn decode(&mut self, buf: &[W]) -> T::Value {
+        while self.num_bits < T::NUM_BITS {
+            self.bits |= u128::from(buf[self.cell].into()) << self.num_bits;
+            self.num_bits += W::NUM_BITS;
+            self.cell += 1;
+        }
+
+        let value = self.bits as u64 & raw::max(T::NUM_BITS);
+        self.bits >>= T::NUM_BITS;
+        self.num_bits -= T::NUM_BITS;
+        T::Value::from_u64(value)
+    }
+
+    /// Moves the decoder to the integer starting at bit `bit` of `buf`, loading its first cell
+    /// if `load` is `true`.
+    fn seek(&mut self, buf: &[W], bit: usize, load: bool) {
+        self.bits = 0;
+        self.num_bits = 0;
+        self.cell = bit / W::NUM_BITS;
+        if load {
+            let start_bit = bit % W::NUM_BITS;
+            self.bits = u128::from(buf[self.cell].into() >> start_bit);
+            self.num_bits = W::NUM_BITS - start_bit;
+            self.cell += 1;
+        }
+    }
+}
+
+impl<T: PackedInt, W: Word> Eq for PackedIntegers<T, W> {}
+
+impl<T: PackedInt, W: Word> PartialEq for PackedIntegers<T, W> {
     fn eq(&self, other: &Self) -> bool {
         self.len == other.len && self.cmp(other) == Ordering::Equal
     }
 }
 
-impl<T: PackedInt> Ord for PackedIntegers<T> {
+impl<T: PackedInt, W: Word> Ord for PackedIntegers<T, W> {
     fn cmp(&self, other: &Self) -> Ordering {
         let mut o_iter = other.iter();
         for s in self {
@@ -645,25 +1949,77 @@ impl<T: PackedInt> Ord for PackedIntegers<T> {
     }
 }
 
-impl<T: PackedInt> PartialOrd for PackedIntegers<T> {
+impl<T: PackedInt, W: Word> PartialOrd for PackedIntegers<T, W> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl<T: PackedInt> Debug for PackedIntegers<T> {
+impl<T: PackedInt, W: Word> Debug for PackedIntegers<T, W> {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         write!(formatter, "(U{}) ", T::NUM_BITS)?;
         formatter.debug_list().entries(self.iter()).finish()
     }
 }
 
+/// Panics with the `ValueOutOfRange` error's message if `value` is greater than `T::MAX`.
+#[inline]
+fn assert_value<T: PackedInt>(value: T::Value) {
+    if let Err(err) = check_value::<T>(value) {
+        panic!("{}", err);
+    }
+}
+
+/// Returns a `ValueOutOfRange` error if `value` is greater than `T::MAX`.
+#[inline]
+fn check_value<T: PackedInt>(value: T::Value) -> Result<(), PackedIntegersError> {
+    if value > T::MAX {
+        Err(PackedIntegersError::ValueOutOfRange {
+            value: value.into(),
+            max: T::MAX.into(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Resolves `range` against a sequence of `len` integers. Panics if it is out of bounds.
+fn to_bounds<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => match start.checked_add(1) {
+            Some(start) => start,
+            None => panic!("attempted to index slice from after maximum usize"),
+        },
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => match end.checked_add(1) {
+            Some(end) => end,
+            None => panic!("attempted to index slice up to maximum usize"),
+        },
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    if start > end {
+        panic!("slice index starts at {} but ends at {}", start, end);
+    }
+    if end > len {
+        panic!(
+            "range end index {} out of range for slice of length {}",
+            end, len
+        );
+    }
+    (start, end)
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! count_integers {
-    (; $type:ident) => { 0 };
-    ($head:expr; $type:ident) => { 1 };
-    ($head:expr, $($tail:expr),*; $type:ident) => {
+    (; $type:ty) => { 0 };
+    ($head:expr; $type:ty) => { 1 };
+    ($head:expr, $($tail:expr),*; $type:ty) => {
         1 + $crate::count_integers!($($tail),*; $type)
     };
 }
@@ -671,10 +2027,10 @@ macro_rules! count_integers {
 /// A macro for a more convenient initialisation of `PackedIntegers`.
 #[macro_export]
 macro_rules! packed_ints {
-    (; $type:ident) => {
-        PackedIntegers::<$type>::new()
+    (; $type:ty) => {
+        $crate::PackedIntegers::<$type>::new()
     };
-    ($($ints:expr),+; $type:ident) => {
+    ($($ints:expr),+; $type:ty) => {
         {
             let capacity = $crate::count_integers!($($ints),+; $type);
             let mut is = $crate::PackedIntegers::<$type>::with_capacity(capacity);
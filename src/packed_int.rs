@@ -1,165 +1,163 @@
-#[derive(Clone)]
-pub enum U1 {}
-#[derive(Clone)]
-pub enum U2 {}
-#[derive(Clone)]
-pub enum U3 {}
-#[derive(Clone)]
-pub enum U4 {}
-#[derive(Clone)]
-pub enum U5 {}
-#[derive(Clone)]
-pub enum U6 {}
-#[derive(Clone)]
-pub enum U7 {}
-#[derive(Clone)]
-pub enum U8 {}
-#[derive(Clone)]
-pub enum U9 {}
-#[derive(Clone)]
-pub enum U10 {}
-#[derive(Clone)]
-pub enum U11 {}
-#[derive(Clone)]
-pub enum U12 {}
-#[derive(Clone)]
-pub enum U13 {}
-#[derive(Clone)]
-pub enum U14 {}
-#[derive(Clone)]
-pub enum U15 {}
-#[derive(Clone)]
-pub enum U16 {}
-#[derive(Clone)]
-pub enum U17 {}
-#[derive(Clone)]
-pub enum U18 {}
-#[derive(Clone)]
-pub enum U19 {}
-#[derive(Clone)]
-pub enum U20 {}
-#[derive(Clone)]
-pub enum U21 {}
-#[derive(Clone)]
-pub enum U22 {}
-#[derive(Clone)]
-pub enum U23 {}
-#[derive(Clone)]
-pub enum U24 {}
-#[derive(Clone)]
-pub enum U25 {}
-#[derive(Clone)]
-pub enum U26 {}
-#[derive(Clone)]
-pub enum U27 {}
-#[derive(Clone)]
-pub enum U28 {}
-#[derive(Clone)]
-pub enum U29 {}
-#[derive(Clone)]
-pub enum U30 {}
-#[derive(Clone)]
-pub enum U31 {}
+use std::fmt::{Debug, Display};
+
+/// A trait for the unsigned integer types that packed integers are read and written as.
+pub trait PackedValue: Copy + Debug + Display + Eq + Ord + Into<u64> {
+    /// The signed integer type of the same size, used by `PackedSignedIntegers`.
+    type Signed: Copy + Debug + Display + Eq + Ord + Into<i64>;
+
+    /// Converts a `u64` into this type, discarding any high bits that do not fit.
+    fn from_u64(value: u64) -> Self;
+
+    /// Converts an `i64` into the signed type, discarding any high bits that do not fit.
+    fn signed_from_i64(value: i64) -> Self::Signed;
+}
+
+impl PackedValue for u32 {
+    type Signed = i32;
+
+    #[inline]
+    fn from_u64(value: u64) -> u32 {
+        value as u32
+    }
+
+    #[inline]
+    fn signed_from_i64(value: i64) -> i32 {
+        value as i32
+    }
+}
+
+impl PackedValue for u64 {
+    type Signed = i64;
+
+    #[inline]
+    fn from_u64(value: u64) -> u64 {
+        value
+    }
+
+    #[inline]
+    fn signed_from_i64(value: i64) -> i64 {
+        value
+    }
+}
 
 /// A trait for packed integer types.
 pub trait PackedInt {
+    /// The unsigned integer type that values of this type are read and written as. This is `u32`
+    /// for types up to `U32`, and `u64` for wider types.
+    type Value: PackedValue;
+
     /// The number of bits in this integer type.
     const NUM_BITS: usize;
 
     /// The largest value this integer type can hold.
-    const MAX: u32 = (1 << Self::NUM_BITS) - 1;
+    const MAX: Self::Value;
 }
 
-impl PackedInt for U1 {
-    const NUM_BITS: usize = 1;
-}
-impl PackedInt for U2 {
-    const NUM_BITS: usize = 2;
-}
-impl PackedInt for U3 {
-    const NUM_BITS: usize = 3;
-}
-impl PackedInt for U4 {
-    const NUM_BITS: usize = 4;
-}
-impl PackedInt for U5 {
-    const NUM_BITS: usize = 5;
-}
-impl PackedInt for U6 {
-    const NUM_BITS: usize = 6;
-}
-impl PackedInt for U7 {
-    const NUM_BITS: usize = 7;
-}
-impl PackedInt for U8 {
-    const NUM_BITS: usize = 8;
-}
-impl PackedInt for U9 {
-    const NUM_BITS: usize = 9;
-}
-impl PackedInt for U10 {
-    const NUM_BITS: usize = 10;
-}
-impl PackedInt for U11 {
-    const NUM_BITS: usize = 11;
-}
-impl PackedInt for U12 {
-    const NUM_BITS: usize = 12;
-}
-impl PackedInt for U13 {
-    const NUM_BITS: usize = 13;
-}
-impl PackedInt for U14 {
-    const NUM_BITS: usize = 14;
-}
-impl PackedInt for U15 {
-    const NUM_BITS: usize = 15;
-}
-impl PackedInt for U16 {
-    const NUM_BITS: usize = 16;
-}
-impl PackedInt for U17 {
-    const NUM_BITS: usize = 17;
-}
-impl PackedInt for U18 {
-    const NUM_BITS: usize = 18;
-}
-impl PackedInt for U19 {
-    const NUM_BITS: usize = 19;
-}
-impl PackedInt for U20 {
-    const NUM_BITS: usize = 20;
-}
-impl PackedInt for U21 {
-    const NUM_BITS: usize = 21;
-}
-impl PackedInt for U22 {
-    const NUM_BITS: usize = 22;
-}
-impl PackedInt for U23 {
-    const NUM_BITS: usize = 23;
-}
-impl PackedInt for U24 {
-    const NUM_BITS: usize = 24;
-}
-impl PackedInt for U25 {
-    const NUM_BITS: usize = 25;
-}
-impl PackedInt for U26 {
-    const NUM_BITS: usize = 26;
-}
-impl PackedInt for U27 {
-    const NUM_BITS: usize = 27;
-}
-impl PackedInt for U28 {
-    const NUM_BITS: usize = 28;
-}
-impl PackedInt for U29 {
-    const NUM_BITS: usize = 29;
-}
-impl PackedInt for U30 {
-    const NUM_BITS: usize = 30;
-}
-impl PackedInt for U31 {
-    const NUM_BITS: usize = 31;
+/// A packed integer type that is `N` bits wide.
+///
+/// `Bits<N>` implements `PackedInt` for every `N` in the range `1..=64`, and `U1` to `U64` are
+/// aliases for `Bits<1>` to `Bits<64>`. This allows widths to be computed in `const` contexts
+/// rather than picked from a list of types. Any other width is rejected at compile time.
+///
+/// # Example
+///
+/// ```
+/// use packed_integers::{Bits, PackedIntegers, U12};
+///
+/// const fn bits_for(max: u32) -> usize {
+///     (u32::BITS - max.leading_zeros()) as usize
+/// }
+///
+/// let mut is = PackedIntegers::<Bits<{ bits_for(4000) }>>::new();
+/// is.push(4000);
+///
+/// // `Bits<12>` and `U12` are the same type.
+/// let is: PackedIntegers<U12> = is;
+/// assert_eq!(is.get(0), Some(4000));
+/// ```
+///
+/// ```compile_fail
+/// use packed_integers::{Bits, PackedIntegers};
+///
+/// // Does not compile, as `Bits<0>` does not implement `PackedInt`.
+/// let is = PackedIntegers::<Bits<0>>::new();
+/// ```
+#[derive(Clone)]
+pub enum Bits<const N: usize> {}
+
+macro_rules! packed_int {
+    ($name:ident, $value:ty, $num_bits:expr) => {
+        pub type $name = Bits<$num_bits>;
+
+        impl PackedInt for Bits<$num_bits> {
+            type Value = $value;
+            const NUM_BITS: usize = $num_bits;
+            const MAX: $value = <$value>::MAX >> (<$value>::BITS as usize - $num_bits);
+        }
+    };
 }
+
+packed_int!(U1, u32, 1);
+packed_int!(U2, u32, 2);
+packed_int!(U3, u32, 3);
+packed_int!(U4, u32, 4);
+packed_int!(U5, u32, 5);
+packed_int!(U6, u32, 6);
+packed_int!(U7, u32, 7);
+packed_int!(U8, u32, 8);
+packed_int!(U9, u32, 9);
+packed_int!(U10, u32, 10);
+packed_int!(U11, u32, 11);
+packed_int!(U12, u32, 12);
+packed_int!(U13, u32, 13);
+packed_int!(U14, u32, 14);
+packed_int!(U15, u32, 15);
+packed_int!(U16, u32, 16);
+packed_int!(U17, u32, 17);
+packed_int!(U18, u32, 18);
+packed_int!(U19, u32, 19);
+packed_int!(U20, u32, 20);
+packed_int!(U21, u32, 21);
+packed_int!(U22, u32, 22);
+packed_int!(U23, u32, 23);
+packed_int!(U24, u32, 24);
+packed_int!(U25, u32, 25);
+packed_int!(U26, u32, 26);
+packed_int!(U27, u32, 27);
+packed_int!(U28, u32, 28);
+packed_int!(U29, u32, 29);
+packed_int!(U30, u32, 30);
+packed_int!(U31, u32, 31);
+packed_int!(U32, u32, 32);
+packed_int!(U33, u64, 33);
+packed_int!(U34, u64, 34);
+packed_int!(U35, u64, 35);
+packed_int!(U36, u64, 36);
+packed_int!(U37, u64, 37);
+packed_int!(U38, u64, 38);
+packed_int!(U39, u64, 39);
+packed_int!(U40, u64, 40);
+packed_int!(U41, u64, 41);
+packed_int!(U42, u64, 42);
+packed_int!(U43, u64, 43);
+packed_int!(U44, u64, 44);
+packed_int!(U45, u64, 45);
+packed_int!(U46, u64, 46);
+packed_int!(U47, u64, 47);
+packed_int!(U48, u64, 48);
+packed_int!(U49, u64, 49);
+packed_int!(U50, u64, 50);
+packed_int!(U51, u64, 51);
+packed_int!(U52, u64, 52);
+packed_int!(U53, u64, 53);
+packed_int!(U54, u64, 54);
+packed_int!(U55, u64, 55);
+packed_int!(U56, u64, 56);
+packed_int!(U57, u64, 57);
+packed_int!(U58, u64, 58);
+packed_int!(U59, u64, 59);
+packed_int!(U60, u64, 60);
+packed_int!(U61, u64, 61);
+packed_int!(U62, u64, 62);
+packed_int!(U63, u64, 63);
+packed_int!(U64, u64, 64);
@@ -0,0 +1,204 @@
+use crate::{
+    assert_value, raw, PackedInt, PackedIntegersIterator, PackedIntegersRef, PackedValue, Word,
+};
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+
+/// A fixed-length, mutable view of packed integers held in a borrowed `&mut [W]` buffer.
+///
+/// `PackedIntegersMut` reads and writes integers laid out exactly as in a `PackedIntegers<T, W>`
+/// buffer, in place. This allows packed integers that live inside a larger arena or a shared
+/// memory segment to be edited without moving them into a `Vec`.
+///
+/// # Example
+///
+/// ```
+/// use packed_integers::{packed_ints, PackedIntegersMut, U9};
+///
+/// let mut buf = packed_ints![507, 508, 509; U9].to_vec();
+/// let mut is = PackedIntegersMut::<U9>::from_slice(&mut buf, 3);
+///
+/// is.set(0, 100);
+/// is.swap(0, 2);
+///
+/// assert_eq!(is.iter().collect::<Vec<_>>(), vec![509, 508, 100]);
+/// ```
+pub struct PackedIntegersMut<'a, T: PackedInt, W: Word = u32> {
+    buf: &'a mut [W],
+    len: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T: PackedInt, W: Word> PackedIntegersMut<'a, T, W> {
+    /// Creates a mutable view of `num_ints` packed integers over a supplied `&mut [W]` buffer.
+    /// Panics if `num_ints * T::NUM_BITS` is greater than the number of bits the buffer has.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedIntegersMut, U8};
+    ///
+    /// let mut buf = [0b00000001_00000010_00000100_00001000];
+    /// let mut is = PackedIntegersMut::<U8>::from_slice(&mut buf, 4);
+    /// is.set(0, 0b1111);
+    ///
+    /// assert_eq!(buf, [0b00000001_00000010_00000100_00001111]);
+    /// ```
+    pub fn from_slice(buf: &'a mut [W], num_ints: usize) -> PackedIntegersMut<'a, T, W> {
+        let min_len = raw::buf_len::<W>(num_ints, T::NUM_BITS);
+        if buf.len() < min_len {
+            panic!(
+                "slice is too small (len {}, should be at least {})",
+                buf.len(),
+                min_len
+            );
+        }
+
+        PackedIntegersMut {
+            buf,
+            len: num_ints,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns a read-only view of the integers.
+    pub fn as_packed_ref(&self) -> PackedIntegersRef<'_, T, W> {
+        PackedIntegersRef::from_slice(self.buf, self.len)
+    }
+
+    /// Sets every integer in the view to `value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedIntegersMut, U9};
+    ///
+    /// let mut buf = [0; 2];
+    /// let mut is = PackedIntegersMut::<U9>::from_slice(&mut buf, 7);
+    /// is.fill(300);
+    ///
+    /// assert_eq!(is.iter().collect::<Vec<_>>(), vec![300; 7]);
+    /// ```
+    pub fn fill(&mut self, value: T::Value) {
+        assert_value::<T>(value);
+
+        for i in 0..self.len {
+            raw::write(self.buf, i * T::NUM_BITS, T::NUM_BITS, value.into());
+        }
+    }
+
+    /// Returns the value of the integer at position `index`, or `None` if out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, PackedIntegersMut, U9};
+    ///
+    /// let mut buf = packed_ints![100, 200, 300; U9].to_vec();
+    /// let is = PackedIntegersMut::<U9>::from_slice(&mut buf, 3);
+    ///
+    /// assert_eq!(is.get(1), Some(200));
+    /// assert_eq!(is.get(3), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<T::Value> {
+        if index >= self.len {
+            None
+        } else {
+            Some(self.get_unchecked(index))
+        }
+    }
+
+    fn get_unchecked(&self, index: usize) -> T::Value {
+        let value = raw::read(self.buf, index * T::NUM_BITS, T::NUM_BITS);
+        T::Value::from_u64(value)
+    }
+
+    /// Returns `true` if the view contains no integers.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the view.
+    pub fn iter(&self) -> PackedIntegersIterator<'_, T, W> {
+        PackedIntegersIterator::new(self.buf, 0, self.len)
+    }
+
+    /// Returns the number of integers in the view.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Sets the integer value at `index` to `value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, PackedIntegersMut, U9};
+    ///
+    /// let mut buf = packed_ints![100, 200, 300; U9].to_vec();
+    /// let mut is = PackedIntegersMut::<U9>::from_slice(&mut buf, 3);
+    /// is.set(1, 400);
+    ///
+    /// assert_eq!(is.get(1), Some(400));
+    /// ```
+    pub fn set(&mut self, index: usize, value: T::Value) {
+        if index >= self.len {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
+            );
+        } else {
+            self.set_unchecked(index, value);
+        }
+    }
+
+    fn set_unchecked(&mut self, index: usize, value: T::Value) {
+        assert_value::<T>(value);
+
+        raw::write(self.buf, index * T::NUM_BITS, T::NUM_BITS, value.into());
+    }
+
+    /// Swaps the integers at positions `a` and `b`. Panics if either is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, PackedIntegersMut, U9};
+    ///
+    /// let mut buf = packed_ints![100, 200, 300; U9].to_vec();
+    /// let mut is = PackedIntegersMut::<U9>::from_slice(&mut buf, 3);
+    /// is.swap(0, 2);
+    ///
+    /// assert_eq!(is.iter().collect::<Vec<_>>(), vec![300, 200, 100]);
+    /// ```
+    pub fn swap(&mut self, a: usize, b: usize) {
+        let index = a.max(b);
+        if index >= self.len {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
+            );
+        }
+
+        let value_a = self.get_unchecked(a);
+        let value_b = self.get_unchecked(b);
+        self.set_unchecked(a, value_b);
+        self.set_unchecked(b, value_a);
+    }
+}
+
+impl<'a, T: PackedInt, W: Word> IntoIterator for &'a PackedIntegersMut<'_, T, W> {
+    type Item = T::Value;
+    type IntoIter = PackedIntegersIterator<'a, T, W>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: PackedInt, W: Word> Debug for PackedIntegersMut<'_, T, W> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "(U{}) ", T::NUM_BITS)?;
+        formatter.debug_list().entries(self.iter()).finish()
+    }
+}
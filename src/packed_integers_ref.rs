@@ -0,0 +1,185 @@
+use crate::{raw, PackedInt, PackedIntegers, PackedIntegersIterator, PackedValue, Word};
+use std::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+
+/// A read-only view of packed integers held in a borrowed `&[W]` buffer.
+///
+/// `PackedIntegersRef` reads integers laid out exactly as in a `PackedIntegers<T, W>` buffer,
+/// without copying them. This allows packed integers in memory-mapped files or network buffers to
+/// be used in place.
+///
+/// # Example
+///
+/// ```
+/// use packed_integers::{packed_ints, PackedIntegersRef, U9};
+///
+/// let buf = packed_ints![507, 508, 509, 510, 511; U9].to_vec();
+/// let is = PackedIntegersRef::<U9>::from_slice(&buf, 5);
+///
+/// assert_eq!(is.len(), 5);
+/// assert_eq!(is.get(1), Some(508));
+/// assert_eq!(is.iter().max(), Some(511));
+/// ```
+pub struct PackedIntegersRef<'a, T: PackedInt, W: Word = u32> {
+    buf: &'a [W],
+    len: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T: PackedInt, W: Word> PackedIntegersRef<'a, T, W> {
+    /// Creates a view of `num_ints` packed integers over a supplied `&[W]` buffer. Panics if
+    /// `num_ints * T::NUM_BITS` is greater than the number of bits the buffer has.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedIntegersRef, U8};
+    ///
+    /// let buf = [0b00000001_00000010_00000100_00001000];
+    /// let is = PackedIntegersRef::<U8>::from_slice(&buf, 4);
+    ///
+    /// assert_eq!(is.get(0), Some(0b1000));
+    /// assert_eq!(is.get(1), Some(0b0100));
+    /// assert_eq!(is.get(2), Some(0b0010));
+    /// assert_eq!(is.get(3), Some(0b0001));
+    /// assert_eq!(is.get(4), None);
+    /// ```
+    pub fn from_slice(buf: &'a [W], num_ints: usize) -> PackedIntegersRef<'a, T, W> {
+        let min_len = raw::buf_len::<W>(num_ints, T::NUM_BITS);
+        if buf.len() < min_len {
+            panic!(
+                "slice is too small (len {}, should be at least {})",
+                buf.len(),
+                min_len
+            );
+        }
+
+        PackedIntegersRef {
+            buf,
+            len: num_ints,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the value of the integer at position `index`, or `None` if out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, PackedIntegersRef, U9};
+    ///
+    /// let buf = packed_ints![100, 200, 300; U9].to_vec();
+    /// let is = PackedIntegersRef::<U9>::from_slice(&buf, 3);
+    ///
+    /// assert_eq!(is.get(1), Some(200));
+    /// assert_eq!(is.get(3), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<T::Value> {
+        if index >= self.len {
+            None
+        } else {
+            let value = raw::read(self.buf, index * T::NUM_BITS, T::NUM_BITS);
+            Some(T::Value::from_u64(value))
+        }
+    }
+
+    /// Returns `true` if the view contains no integers.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the view.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, PackedIntegersRef, U9};
+    ///
+    /// let buf = packed_ints![509, 510, 511; U9].to_vec();
+    /// let is = PackedIntegersRef::<U9>::from_slice(&buf, 3);
+    /// let mut iter = is.iter();
+    ///
+    /// assert_eq!(iter.next(), Some(509));
+    /// assert_eq!(iter.next(), Some(510));
+    /// assert_eq!(iter.next(), Some(511));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn iter(&self) -> PackedIntegersIterator<'a, T, W> {
+        PackedIntegersIterator::new(self.buf, 0, self.len)
+    }
+
+    /// Returns the number of integers in the view.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Copies the integers into a new `PackedIntegers<T, W>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, PackedIntegersRef, U9};
+    ///
+    /// let buf = packed_ints![100, 200, 300; U9].to_vec();
+    /// let is = PackedIntegersRef::<U9>::from_slice(&buf, 2);
+    ///
+    /// assert_eq!(is.to_packed(), packed_ints![100, 200; U9]);
+    /// ```
+    pub fn to_packed(&self) -> PackedIntegers<T, W> {
+        PackedIntegers::from_vec(raw::to_vec(self.buf, self.len * T::NUM_BITS), self.len)
+    }
+}
+
+impl<T: PackedInt, W: Word> Clone for PackedIntegersRef<'_, T, W> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: PackedInt, W: Word> Copy for PackedIntegersRef<'_, T, W> {}
+
+impl<'a, T: PackedInt, W: Word> IntoIterator for PackedIntegersRef<'a, T, W> {
+    type Item = T::Value;
+    type IntoIter = PackedIntegersIterator<'a, T, W>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: PackedInt, W: Word> IntoIterator for &PackedIntegersRef<'a, T, W> {
+    type Item = T::Value;
+    type IntoIter = PackedIntegersIterator<'a, T, W>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: PackedInt, W: Word> Eq for PackedIntegersRef<'_, T, W> {}
+
+impl<T: PackedInt, W: Word> PartialEq for PackedIntegersRef<'_, T, W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: PackedInt, W: Word> Ord for PackedIntegersRef<'_, T, W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: PackedInt, W: Word> PartialOrd for PackedIntegersRef<'_, T, W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PackedInt, W: Word> Debug for PackedIntegersRef<'_, T, W> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "(U{}) ", T::NUM_BITS)?;
+        formatter.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,348 @@
+use crate::{
+    PackedInt, PackedIntegers, PackedIntegersIntoIterator, PackedIntegersIterator, PackedValue,
+};
+use std::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
+use std::fmt::{self, Debug, Formatter};
+
+type Signed<T> = <<T as PackedInt>::Value as PackedValue>::Signed;
+
+/// A growable array of packed signed integers, backed by a `Vec<u32>` buffer.
+///
+/// Values of a `T`-bit type are in the range `-2^(T::NUM_BITS - 1)..2^(T::NUM_BITS - 1)`, and are
+/// read and written as `i32` for types up to `U32`, and `i64` for wider types. Values are stored
+/// zigzag-encoded, so that integers close to zero use the low bits regardless of their sign.
+///
+/// # Examples
+///
+/// ```
+/// use packed_integers::{PackedSignedIntegers, U9};
+///
+/// let mut is = PackedSignedIntegers::<U9>::new();
+/// is.push(-256);
+/// is.push(255);
+///
+/// assert_eq!(is.len(), 2);
+/// assert_eq!(is.get(0), Some(-256));
+///
+/// assert_eq!(is.pop(), Some(255));
+/// assert_eq!(is.len(), 1);
+///
+/// // This will panic, as -257 is outside the range -256..=255.
+/// // is.push(-257);
+/// ```
+#[derive(Clone)]
+pub struct PackedSignedIntegers<T: PackedInt> {
+    vec: PackedIntegers<T>,
+}
+
+impl<T: PackedInt> Default for PackedSignedIntegers<T> {
+    fn default() -> PackedSignedIntegers<T> {
+        PackedSignedIntegers::new()
+    }
+}
+
+impl<T: PackedInt> PackedSignedIntegers<T> {
+    /// Constructs a new, empty `PackedSignedIntegers<T>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedSignedIntegers, U9};
+    ///
+    /// let mut is = PackedSignedIntegers::<U9>::new();
+    /// ```
+    pub fn new() -> PackedSignedIntegers<T> {
+        PackedSignedIntegers {
+            vec: PackedIntegers::new(),
+        }
+    }
+
+    /// Constructs a new, empty `PackedSignedIntegers<T>` with _at least_ the specified capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedSignedIntegers, U8};
+    ///
+    /// let mut is = PackedSignedIntegers::<U8>::with_capacity(1);
+    ///
+    /// assert_eq!(is.capacity(), 4);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> PackedSignedIntegers<T> {
+        PackedSignedIntegers {
+            vec: PackedIntegers::with_capacity(capacity),
+        }
+    }
+
+    /// Moves all integers of `other` into `Self`, leaving `other` empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedSignedIntegers, U8};
+    ///
+    /// let mut is1 = PackedSignedIntegers::<U8>::new();
+    /// is1.push(-10);
+    /// let mut is2 = PackedSignedIntegers::<U8>::new();
+    /// is2.push(20);
+    /// is1.append(&mut is2);
+    ///
+    /// assert_eq!(is1.iter().collect::<Vec<_>>(), vec![-10, 20]);
+    /// assert!(is2.is_empty());
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        self.vec.append(&mut other.vec);
+    }
+
+    /// Returns the number of integers the vector can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    /// Clears the vector. This method does not affect the vector's allocated capacity.
+    pub fn clear(&mut self) {
+        self.vec.clear()
+    }
+
+    /// Returns the value of the integer at position `index`, or `None` if out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedSignedIntegers, U9};
+    ///
+    /// let mut is = PackedSignedIntegers::<U9>::new();
+    /// is.push(-100);
+    ///
+    /// assert_eq!(is.get(0), Some(-100));
+    /// assert_eq!(is.get(1), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<Signed<T>> {
+        self.vec.get(index).map(Self::decode)
+    }
+
+    /// Inserts an integer at position `index`, shifting all integers after it to the right.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedSignedIntegers, U8};
+    ///
+    /// let mut is = PackedSignedIntegers::<U8>::new();
+    /// is.push(10);
+    /// is.push(-30);
+    /// is.insert(1, -20);
+    ///
+    /// assert_eq!(is.iter().collect::<Vec<_>>(), vec![10, -20, -30]);
+    /// ```
+    pub fn insert(&mut self, index: usize, value: Signed<T>) {
+        self.vec.insert(index, Self::encode(value));
+    }
+
+    /// Returns `true` if the vector contains no integers.
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// Returns an iterator over the vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedSignedIntegers, U9};
+    ///
+    /// let mut is = PackedSignedIntegers::<U9>::new();
+    /// is.push(-1);
+    /// is.push(1);
+    /// let mut iter = is.iter();
+    ///
+    /// assert_eq!(iter.next(), Some(-1));
+    /// assert_eq!(iter.next(), Some(1));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn iter(&self) -> PackedSignedIntegersIterator<'_, T> {
+        self.into_iter()
+    }
+
+    /// Returns the number of integers in the vector.
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Removes the last integer from the vector and returns it, or `None` if empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedSignedIntegers, U10};
+    ///
+    /// let mut is = PackedSignedIntegers::<U10>::new();
+    /// is.push(-300);
+    ///
+    /// assert_eq!(is.pop(), Some(-300));
+    /// assert_eq!(is.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<Signed<T>> {
+        self.vec.pop().map(Self::decode)
+    }
+
+    /// Appends an integer to the back of the vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedSignedIntegers, U10};
+    ///
+    /// let mut is = PackedSignedIntegers::<U10>::new();
+    /// is.push(-512);
+    /// is.push(511);
+    ///
+    /// assert_eq!(is.iter().collect::<Vec<_>>(), vec![-512, 511]);
+    /// ```
+    pub fn push(&mut self, value: Signed<T>) {
+        self.vec.push(Self::encode(value));
+    }
+
+    /// Removes and returns the integer at position `index`, shifting all integers after it to the
+    /// left.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedSignedIntegers, U8};
+    ///
+    /// let mut is = PackedSignedIntegers::<U8>::new();
+    /// is.push(-10);
+    /// is.push(-20);
+    ///
+    /// assert_eq!(is.remove(0), -10);
+    /// assert_eq!(is.iter().collect::<Vec<_>>(), vec![-20]);
+    /// ```
+    pub fn remove(&mut self, index: usize) -> Signed<T> {
+        Self::decode(self.vec.remove(index))
+    }
+
+    /// Reserves capacity for _at least_ `additional` more integers to be inserted.
+    pub fn reserve(&mut self, additional: usize) {
+        self.vec.reserve(additional)
+    }
+
+    /// Sets the integer value at `index` to `value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedSignedIntegers, U9};
+    ///
+    /// let mut is = PackedSignedIntegers::<U9>::new();
+    /// is.push(100);
+    /// is.set(0, -100);
+    ///
+    /// assert_eq!(is.get(0), Some(-100));
+    /// ```
+    pub fn set(&mut self, index: usize, value: Signed<T>) {
+        self.vec.set(index, Self::encode(value));
+    }
+
+    /// Returns a copy of the backing `Vec<u32>` buffer, holding the zigzag-encoded integers.
+    pub fn to_vec(&self) -> Vec<u32> {
+        self.vec.to_vec()
+    }
+
+    /// Keeps the first `len` integers, and drops the rest.
+    pub fn truncate(&mut self, len: usize) {
+        self.vec.truncate(len)
+    }
+
+    #[inline]
+    fn decode(value: T::Value) -> Signed<T> {
+        let value: u64 = value.into();
+        T::Value::signed_from_i64((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    #[inline]
+    fn encode(value: Signed<T>) -> T::Value {
+        let value: i64 = value.into();
+        let max = (T::MAX.into() >> 1) as i64;
+        if value < -max - 1 || value > max {
+            panic!("value is outside the range {}..={}", -max - 1, max);
+        }
+
+        T::Value::from_u64(((value << 1) ^ (value >> 63)) as u64)
+    }
+}
+
+/// A consuming iterator for `PackedSignedIntegers`.
+pub struct PackedSignedIntegersIntoIterator<T: PackedInt> {
+    iter: PackedIntegersIntoIterator<T>,
+}
+
+impl<T: PackedInt> IntoIterator for PackedSignedIntegers<T> {
+    type Item = Signed<T>;
+    type IntoIter = PackedSignedIntegersIntoIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        PackedSignedIntegersIntoIterator {
+            iter: self.vec.into_iter(),
+        }
+    }
+}
+
+impl<T: PackedInt> Iterator for PackedSignedIntegersIntoIterator<T> {
+    type Item = Signed<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(PackedSignedIntegers::<T>::decode)
+    }
+}
+
+/// An iterator for `PackedSignedIntegers`.
+pub struct PackedSignedIntegersIterator<'a, T: PackedInt> {
+    iter: PackedIntegersIterator<'a, T>,
+}
+
+impl<'a, T: PackedInt> IntoIterator for &'a PackedSignedIntegers<T> {
+    type Item = Signed<T>;
+    type IntoIter = PackedSignedIntegersIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        PackedSignedIntegersIterator {
+            iter: self.vec.iter(),
+        }
+    }
+}
+
+impl<T: PackedInt> Iterator for PackedSignedIntegersIterator<'_, T> {
+    type Item = Signed<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(PackedSignedIntegers::<T>::decode)
+    }
+}
+
+impl<T: PackedInt> Eq for PackedSignedIntegers<T> {}
+
+impl<T: PackedInt> PartialEq for PackedSignedIntegers<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.vec == other.vec
+    }
+}
+
+impl<T: PackedInt> Ord for PackedSignedIntegers<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: PackedInt> PartialOrd for PackedSignedIntegers<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PackedInt> Debug for PackedSignedIntegers<T> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "(I{}) ", T::NUM_BITS)?;
+        formatter.debug_list().entries(self.iter()).finish()
+    }
+}
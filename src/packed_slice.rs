@@ -0,0 +1,210 @@
+use crate::{raw, to_bounds, PackedInt, PackedIntegers, PackedIntegersIterator, PackedValue, Word};
+use std::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+use std::ops::RangeBounds;
+
+/// A read-only slice of packed integers, borrowed from a `PackedIntegers<T, W>`.
+///
+/// A `PackedSlice` refers to a range of integers without copying them, and can itself be sliced
+/// and split, much like `&[u32]`.
+///
+/// # Example
+///
+/// ```
+/// use packed_integers::{packed_ints, PackedSlice, U9};
+///
+/// fn sum(slice: PackedSlice<'_, U9>) -> u32 {
+///     if slice.len() <= 1 {
+///         return slice.first().unwrap_or(0);
+///     }
+///     let (lo, hi) = slice.split_at(slice.len() / 2);
+///     sum(lo) + sum(hi)
+/// }
+///
+/// let is = packed_ints![100, 200, 300, 400, 500; U9];
+///
+/// assert_eq!(sum(is.slice(..)), 1500);
+/// assert_eq!(sum(is.slice(1..3)), 500);
+/// ```
+pub struct PackedSlice<'a, T: PackedInt, W: Word = u32> {
+    buf: &'a [W],
+    offset: usize,
+    len: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T: PackedInt, W: Word> PackedSlice<'a, T, W> {
+    /// Creates a slice of `len` integers, starting `offset` bits into `buf`.
+    pub(crate) fn new(buf: &'a [W], offset: usize, len: usize) -> PackedSlice<'a, T, W> {
+        // Drop any leading cells, so that the offset always falls within the first cell.
+        let buf = &buf[(offset / W::NUM_BITS).min(buf.len())..];
+
+        PackedSlice {
+            buf,
+            offset: offset % W::NUM_BITS,
+            len,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the first integer of the slice, or `None` if empty.
+    pub fn first(&self) -> Option<T::Value> {
+        self.get(0)
+    }
+
+    /// Returns the value of the integer at position `index`, or `None` if out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let is = packed_ints![100, 200, 300; U9];
+    /// let slice = is.slice(1..);
+    ///
+    /// assert_eq!(slice.get(1), Some(300));
+    /// assert_eq!(slice.get(2), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<T::Value> {
+        if index >= self.len {
+            None
+        } else {
+            let value = raw::read(self.buf, self.offset + index * T::NUM_BITS, T::NUM_BITS);
+            Some(T::Value::from_u64(value))
+        }
+    }
+
+    /// Returns `true` if the slice contains no integers.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the slice.
+    pub fn iter(&self) -> PackedIntegersIterator<'a, T, W> {
+        PackedIntegersIterator::new(self.buf, self.offset, self.len)
+    }
+
+    /// Returns the last integer of the slice, or `None` if empty.
+    pub fn last(&self) -> Option<T::Value> {
+        if self.len == 0 {
+            None
+        } else {
+            self.get(self.len - 1)
+        }
+    }
+
+    /// Returns the number of integers in the slice.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a slice of the integers in `range`, relative to the start of this slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let is = packed_ints![100, 200, 300, 400, 500; U9];
+    /// let slice = is.slice(1..).slice(..2);
+    ///
+    /// assert_eq!(slice.to_packed(), packed_ints![200, 300; U9]);
+    /// ```
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> PackedSlice<'a, T, W> {
+        let (start, end) = to_bounds(range, self.len);
+        PackedSlice::new(self.buf, self.offset + start * T::NUM_BITS, end - start)
+    }
+
+    /// Divides the slice in two at position `mid`. Panics if `mid > len`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let is = packed_ints![100, 200, 300; U9];
+    /// let (lo, hi) = is.slice(..).split_at(1);
+    ///
+    /// assert_eq!(lo.to_packed(), packed_ints![100; U9]);
+    /// assert_eq!(hi.to_packed(), packed_ints![200, 300; U9]);
+    /// ```
+    pub fn split_at(&self, mid: usize) -> (PackedSlice<'a, T, W>, PackedSlice<'a, T, W>) {
+        if mid > self.len {
+            panic!("mid (is {}) should be <= len (is {})", mid, self.len);
+        }
+
+        (self.slice(..mid), self.slice(mid..))
+    }
+
+    /// Copies the integers into a new `PackedIntegers<T, W>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let is = packed_ints![100, 200, 300; U9];
+    ///
+    /// assert_eq!(is.slice(1..).to_packed(), packed_ints![200, 300; U9]);
+    /// ```
+    pub fn to_packed(&self) -> PackedIntegers<T, W> {
+        let mut buf = Vec::new();
+        raw::append_bits(&mut buf, 0, self.buf, self.offset, self.len * T::NUM_BITS);
+
+        PackedIntegers::from_vec(buf, self.len)
+    }
+}
+
+impl<T: PackedInt, W: Word> Clone for PackedSlice<'_, T, W> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: PackedInt, W: Word> Copy for PackedSlice<'_, T, W> {}
+
+impl<'a, T: PackedInt, W: Word> IntoIterator for PackedSlice<'a, T, W> {
+    type Item = T::Value;
+    type IntoIter = PackedIntegersIterator<'a, T, W>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: PackedInt, W: Word> IntoIterator for &PackedSlice<'a, T, W> {
+    type Item = T::Value;
+    type IntoIter = PackedIntegersIterator<'a, T, W>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: PackedInt, W: Word> Eq for PackedSlice<'_, T, W> {}
+
+impl<T: PackedInt, W: Word> PartialEq for PackedSlice<'_, T, W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: PackedInt, W: Word> Ord for PackedSlice<'_, T, W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: PackedInt, W: Word> PartialOrd for PackedSlice<'_, T, W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PackedInt, W: Word> Debug for PackedSlice<'_, T, W> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "(U{}) ", T::NUM_BITS)?;
+        formatter.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,427 @@
+use crate::{assert_value, raw, to_bounds, PackedInt, PackedIntegers, PackedValue, Word};
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+use std::ops::RangeBounds;
+use std::slice;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The most cells an integer can span: 64 bits starting at the last bit of a `u8` cell.
+const MAX_CELLS: usize = 9;
+
+/// A mutable slice of packed integers, borrowed from a `PackedIntegers<T, W>`.
+///
+/// A `PackedSliceMut` can be split into disjoint halves with `split_at_mut`, each of which can be
+/// written independently, and from different threads. Integers rarely end on a word boundary, so
+/// neighbouring halves will often share a buffer cell; writes to the first and last cell of a
+/// slice only ever touch the bits the slice owns, and do so atomically.
+///
+/// # Example
+///
+/// ```
+/// use packed_integers::{packed_ints, U9};
+///
+/// let mut is = packed_ints![0, 0, 0, 0, 0, 0, 0, 0, 0, 0; U9];
+///
+/// let (mut lo, mut hi) = is.split_at_mut(5);
+/// std::thread::scope(|s| {
+///     s.spawn(|| lo.fill(100));
+///     s.spawn(|| hi.fill(200));
+/// });
+///
+/// assert_eq!(is.iter().collect::<Vec<_>>(), [[100; 5], [200; 5]].concat());
+/// ```
+pub struct PackedSliceMut<'a, T: PackedInt, W: Word = u32> {
+    ptr: *mut W,
+    cells: usize,
+    offset: usize,
+    len: usize,
+    phantom: PhantomData<(&'a mut [W], T)>,
+}
+
+// A slice only writes the bits it owns, and accesses to the cells it may share with a neighbour
+// are atomic.
+unsafe impl<T: PackedInt, W: Word> Send for PackedSliceMut<'_, T, W> {}
+unsafe impl<T: PackedInt, W: Word> Sync for PackedSliceMut<'_, T, W> {}
+
+impl<'a, T: PackedInt, W: Word> PackedSliceMut<'a, T, W> {
+    /// Creates a slice of `len` integers, starting `offset` bits into `buf`.
+    pub(crate) fn new(buf: &'a mut [W], offset: usize, len: usize) -> PackedSliceMut<'a, T, W> {
+        assert!(offset + len * T::NUM_BITS <= buf.len() * W::NUM_BITS);
+        unsafe { PackedSliceMut::from_raw_parts(buf.as_mut_ptr(), offset, len) }
+    }
+
+    /// Creates a slice of `len` integers, starting `offset` bits past `ptr`. The caller must
+    /// ensure the bits are valid for `'a`, and not written through any other slice.
+    unsafe fn from_raw_parts(ptr: *mut W, offset: usize, len: usize) -> PackedSliceMut<'a, T, W> {
+        let start_bit = offset % W::NUM_BITS;
+        let cells = (start_bit + len * T::NUM_BITS).div_ceil(W::NUM_BITS);
+
+        PackedSliceMut {
+            // Skip any leading cells, so that the offset always falls within the first cell.
+            ptr: ptr.add(offset / W::NUM_BITS),
+            cells,
+            offset: start_bit,
+            len,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sets every integer in the slice to `value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300, 400; U9];
+    /// is.slice_mut(1..3).fill(500);
+    ///
+    /// assert_eq!(is, packed_ints![100, 500, 500, 400; U9]);
+    /// ```
+    pub fn fill(&mut self, value: T::Value) {
+        assert_value::<T>(value);
+
+        for i in 0..self.len {
+            self.write(self.offset + i * T::NUM_BITS, value.into());
+        }
+    }
+
+    /// Returns the first integer of the slice, or `None` if empty.
+    pub fn first(&self) -> Option<T::Value> {
+        self.get(0)
+    }
+
+    /// Returns the value of the integer at position `index`, or `None` if out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300; U9];
+    /// let slice = is.slice_mut(1..);
+    ///
+    /// assert_eq!(slice.get(1), Some(300));
+    /// assert_eq!(slice.get(2), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<T::Value> {
+        if index >= self.len {
+            None
+        } else {
+            Some(self.get_unchecked(index))
+        }
+    }
+
+    fn get_unchecked(&self, index: usize) -> T::Value {
+        T::Value::from_u64(self.read(self.offset + index * T::NUM_BITS))
+    }
+
+    /// Returns `true` if the slice contains no integers.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the slice.
+    pub fn iter(&self) -> PackedSliceMutIterator<'_, 'a, T, W> {
+        PackedSliceMutIterator {
+            slice: self,
+            index: 0,
+        }
+    }
+
+    /// Returns the last integer of the slice, or `None` if empty.
+    pub fn last(&self) -> Option<T::Value> {
+        if self.len == 0 {
+            None
+        } else {
+            self.get(self.len - 1)
+        }
+    }
+
+    /// Returns the number of integers in the slice.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Sets the integer value at `index` to `value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300; U9];
+    /// is.slice_mut(1..).set(1, 400);
+    ///
+    /// assert_eq!(is, packed_ints![100, 200, 400; U9]);
+    /// ```
+    pub fn set(&mut self, index: usize, value: T::Value) {
+        if index >= self.len {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
+            );
+        } else {
+            self.set_unchecked(index, value);
+        }
+    }
+
+    fn set_unchecked(&mut self, index: usize, value: T::Value) {
+        assert_value::<T>(value);
+
+        self.write(self.offset + index * T::NUM_BITS, value.into());
+    }
+
+    /// Returns a mutable slice of the integers in `range`, relative to the start of this slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300, 400, 500; U9];
+    /// is.slice_mut(1..).slice_mut(..2).fill(0);
+    ///
+    /// assert_eq!(is, packed_ints![100, 0, 0, 400, 500; U9]);
+    /// ```
+    pub fn slice_mut<R: RangeBounds<usize>>(&mut self, range: R) -> PackedSliceMut<'_, T, W> {
+        let (start, end) = to_bounds(range, self.len);
+        unsafe {
+            PackedSliceMut::from_raw_parts(self.ptr, self.offset + start * T::NUM_BITS, end - start)
+        }
+    }
+
+    /// Divides the slice in two mutable slices at position `mid`. Panics if `mid > len`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300, 400; U9];
+    /// let mut slice = is.slice_mut(1..);
+    /// let (mut lo, mut hi) = slice.split_at_mut(1);
+    /// lo.set(0, 1);
+    /// hi.set(0, 2);
+    ///
+    /// assert_eq!(is, packed_ints![100, 1, 2, 400; U9]);
+    /// ```
+    pub fn split_at_mut(
+        &mut self,
+        mid: usize,
+    ) -> (PackedSliceMut<'_, T, W>, PackedSliceMut<'_, T, W>) {
+        self.slice_mut(..).split(mid)
+    }
+
+    /// Swaps the integers at positions `a` and `b`. Panics if either is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let mut is = packed_ints![100, 200, 300; U9];
+    /// is.slice_mut(1..).swap(0, 1);
+    ///
+    /// assert_eq!(is, packed_ints![100, 300, 200; U9]);
+    /// ```
+    pub fn swap(&mut self, a: usize, b: usize) {
+        let index = a.max(b);
+        if index >= self.len {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
+            );
+        }
+
+        let value_a = self.get_unchecked(a);
+        let value_b = self.get_unchecked(b);
+        self.set_unchecked(a, value_b);
+        self.set_unchecked(b, value_a);
+    }
+
+    /// Copies the integers into a new `PackedIntegers<T, W>`.
+    pub fn to_packed(&self) -> PackedIntegers<T, W> {
+        let mut buf = vec![W::default(); raw::buf_len::<W>(self.len, T::NUM_BITS)];
+        for i in 0..self.len {
+            let value = self.read(self.offset + i * T::NUM_BITS);
+            raw::write(&mut buf, i * T::NUM_BITS, T::NUM_BITS, value);
+        }
+
+        PackedIntegers::from_vec(buf, self.len)
+    }
+
+    /// Consumes the slice, dividing it in two at position `mid`. Panics if `mid > len`.
+    pub(crate) fn split(self, mid: usize) -> (PackedSliceMut<'a, T, W>, PackedSliceMut<'a, T, W>) {
+        if mid > self.len {
+            panic!("mid (is {}) should be <= len (is {})", mid, self.len);
+        }
+
+        let mid_bit = self.offset + mid * T::NUM_BITS;
+        unsafe {
+            (
+                PackedSliceMut::from_raw_parts(self.ptr, self.offset, mid),
+                PackedSliceMut::from_raw_parts(self.ptr, mid_bit, self.len - mid),
+            )
+        }
+    }
+
+    /// Reads the integer starting at bit `bit` of the slice's first cell.
+    fn read(&self, bit: usize) -> u64 {
+        let (first, last) = cell_range::<T, W>(bit);
+        if first > 0 && last + 1 < self.cells {
+            return raw::read(self.interior(), bit - W::NUM_BITS, T::NUM_BITS);
+        }
+
+        let mut cells = [W::default(); MAX_CELLS];
+        for (i, cell) in cells[..=last - first].iter_mut().enumerate() {
+            *cell = W::from_u64(self.load(first + i));
+        }
+        raw::read(&cells, bit % W::NUM_BITS, T::NUM_BITS)
+    }
+
+    /// Writes `value` as the integer starting at bit `bit` of the slice's first cell.
+    fn write(&mut self, bit: usize, value: u64) {
+        let (first, last) = cell_range::<T, W>(bit);
+        if first > 0 && last + 1 < self.cells {
+            raw::write(self.interior_mut(), bit - W::NUM_BITS, T::NUM_BITS, value);
+            return;
+        }
+
+        // Lay the integer and its mask out over copies of the cells it spans, then merge each
+        // cell back in, so that bits owned by a neighbouring slice are never written.
+        let mut cells = [W::default(); MAX_CELLS];
+        let mut masks = [W::default(); MAX_CELLS];
+        raw::write(&mut cells, bit % W::NUM_BITS, T::NUM_BITS, value);
+        raw::write(
+            &mut masks,
+            bit % W::NUM_BITS,
+            T::NUM_BITS,
+            raw::max(T::NUM_BITS),
+        );
+        for i in 0..=last - first {
+            self.store(first + i, masks[i].into(), cells[i].into());
+        }
+    }
+
+    /// Returns the cells between the first and last, which no other slice can share.
+    #[inline]
+    fn interior(&self) -> &[W] {
+        unsafe { slice::from_raw_parts(self.ptr.add(1), self.cells.saturating_sub(2)) }
+    }
+
+    #[inline]
+    fn interior_mut(&mut self) -> &mut [W] {
+        unsafe { slice::from_raw_parts_mut(self.ptr.add(1), self.cells.saturating_sub(2)) }
+    }
+
+    /// Returns `true` if the cell at `index` may be shared with a neighbouring slice.
+    #[inline]
+    fn is_shared(&self, index: usize) -> bool {
+        index == 0 || index + 1 == self.cells
+    }
+
+    #[inline]
+    fn load(&self, index: usize) -> u64 {
+        debug_assert!(index < self.cells);
+        unsafe {
+            let cell = self.ptr.add(index);
+            if self.is_shared(index) {
+                atomic_load(cell)
+            } else {
+                (*cell).into()
+            }
+        }
+    }
+
+    /// Replaces the bits of the cell at `index` selected by `mask` with those of `value`.
+    #[inline]
+    fn store(&mut self, index: usize, mask: u64, value: u64) {
+        debug_assert!(index < self.cells);
+        let mask = mask & raw::max(W::NUM_BITS);
+        unsafe {
+            let cell = self.ptr.add(index);
+            if self.is_shared(index) {
+                atomic_store(cell, mask, value & mask);
+            } else {
+                *cell = W::from_u64(((*cell).into() & !mask) | (value & mask));
+            }
+        }
+    }
+}
+
+/// Returns the indices of the first and last cell spanned by the integer starting at bit `bit`.
+#[inline]
+fn cell_range<T: PackedInt, W: Word>(bit: usize) -> (usize, usize) {
+    (bit / W::NUM_BITS, (bit + T::NUM_BITS - 1) / W::NUM_BITS)
+}
+
+/// Loads a cell one byte at a time, so that `W` need not have an atomic counterpart with the
+/// same alignment.
+unsafe fn atomic_load<W: Word>(cell: *mut W) -> u64 {
+    let mut value = 0;
+    for byte in 0..W::NUM_BITS / 8 {
+        let atomic = AtomicU8::from_ptr(byte_ptr(cell, byte));
+        value |= (atomic.load(Ordering::Relaxed) as u64) << (byte * 8);
+    }
+    value
+}
+
+/// Replaces the bits of a cell selected by `mask` with those of `value`, leaving every other
+/// bit untouched even if it is written concurrently.
+unsafe fn atomic_store<W: Word>(cell: *mut W, mask: u64, value: u64) {
+    for byte in 0..W::NUM_BITS / 8 {
+        let byte_mask = (mask >> (byte * 8)) as u8;
+        let byte_value = (value >> (byte * 8)) as u8;
+        let atomic = AtomicU8::from_ptr(byte_ptr(cell, byte));
+        if byte_mask == u8::MAX {
+            atomic.store(byte_value, Ordering::Relaxed);
+        } else if byte_mask != 0 {
+            atomic.fetch_and(!byte_mask, Ordering::Relaxed);
+            atomic.fetch_or(byte_value, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Returns a pointer to the byte holding bits `byte * 8..byte * 8 + 8` of a cell.
+#[inline]
+unsafe fn byte_ptr<W: Word>(cell: *mut W, byte: usize) -> *mut u8 {
+    if cfg!(target_endian = "little") {
+        (cell as *mut u8).add(byte)
+    } else {
+        (cell as *mut u8).add(W::NUM_BITS / 8 - 1 - byte)
+    }
+}
+
+/// An iterator for `PackedSliceMut`.
+pub struct PackedSliceMutIterator<'b, 'a, T: PackedInt, W: Word = u32> {
+    slice: &'b PackedSliceMut<'a, T, W>,
+    index: usize,
+}
+
+impl<'b, 'a, T: PackedInt, W: Word> IntoIterator for &'b PackedSliceMut<'a, T, W> {
+    type Item = T::Value;
+    type IntoIter = PackedSliceMutIterator<'b, 'a, T, W>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: PackedInt, W: Word> Iterator for PackedSliceMutIterator<'_, '_, T, W> {
+    type Item = T::Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.slice.get(self.index)?;
+        self.index += 1;
+
+        Some(result)
+    }
+}
+
+impl<T: PackedInt, W: Word> Debug for PackedSliceMut<'_, T, W> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "(U{}) ", T::NUM_BITS)?;
+        formatter.debug_list().entries(self.iter()).finish()
+    }
+}
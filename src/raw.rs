@@ -0,0 +1,206 @@
+//! Bit-level helpers shared by the packed integer containers. Positions are given in bits from
+//! the start of the buffer, and widths are in the range `1..=64`.
+
+use crate::Word;
+
+/// Returns the largest value an integer of `num_bits` bits can hold.
+#[inline]
+pub(crate) fn max(num_bits: usize) -> u64 {
+    u64::MAX >> (64 - num_bits)
+}
+
+/// Returns the number of buffer cells needed to hold `len` integers of `num_bits` bits.
+#[inline]
+pub(crate) fn buf_len<W: Word>(len: usize, num_bits: usize) -> usize {
+    (len * num_bits).div_ceil(W::NUM_BITS)
+}
+
+/// Reads the `num_bits`-bit integer starting at bit `bit`.
+#[inline]
+pub(crate) fn read<W: Word>(buf: &[W], bit: usize, num_bits: usize) -> u64 {
+    let mut buf_index = bit / W::NUM_BITS;
+    let start_bit = bit % W::NUM_BITS;
+    let mut read_bits = W::NUM_BITS - start_bit;
+
+    let mut value = buf[buf_index].into() >> start_bit;
+    while read_bits < num_bits {
+        // Value spans multiple buffer cells.
+        buf_index += 1;
+        value |= buf[buf_index].into() << read_bits;
+        read_bits += W::NUM_BITS;
+    }
+
+    value & max(num_bits)
+}
+
+/// Writes `value` as the `num_bits`-bit integer starting at bit `bit`. `value` must not exceed
+/// `max(num_bits)`.
+#[inline]
+pub(crate) fn write<W: Word>(buf: &mut [W], bit: usize, num_bits: usize, value: u64) {
+    let mut buf_index = bit / W::NUM_BITS;
+    let start_bit = bit % W::NUM_BITS;
+    let mut written_bits = W::NUM_BITS - start_bit;
+
+    let mask = max(num_bits);
+    let cell = buf[buf_index].into();
+    buf[buf_index] = W::from_u64((cell & !(mask << start_bit)) | (value << start_bit));
+    while written_bits < num_bits {
+        // Value spans multiple buffer cells.
+        buf_index += 1;
+        let cell = buf[buf_index].into();
+        buf[buf_index] = W::from_u64((cell & !(mask >> written_bits)) | (value >> written_bits));
+        written_bits += W::NUM_BITS;
+    }
+}
+
+/// Appends the `num_bits` bits of `src` starting at bit `src_bit` to `dst`, which holds
+/// `dst_bits` bits. Any bits of `dst` past the end are cleared.
+///
+/// Whole cells are copied when both positions are cell-aligned; otherwise each cell is assembled
+/// from two neighbouring source cells.
+pub(crate) fn append_bits<W: Word>(
+    dst: &mut Vec<W>,
+    dst_bits: usize,
+    src: &[W],
+    src_bit: usize,
+    num_bits: usize,
+) {
+    let total_bits = dst_bits + num_bits;
+    dst.truncate(dst_bits.div_ceil(W::NUM_BITS));
+    dst.reserve(total_bits.div_ceil(W::NUM_BITS) - dst.len());
+
+    let src_index = src_bit / W::NUM_BITS;
+    let src_shift = src_bit % W::NUM_BITS;
+    let dst_shift = dst_bits % W::NUM_BITS;
+    let num_cells = num_bits.div_ceil(W::NUM_BITS);
+
+    if src_shift == 0 && dst_shift == 0 {
+        dst.extend_from_slice(&src[src_index..src_index + num_cells]);
+    } else {
+        for i in src_index..src_index + num_cells {
+            let mut cell = src[i].into() >> src_shift;
+            if src_shift > 0 && i + 1 < src.len() {
+                cell |= src[i + 1].into() << (W::NUM_BITS - src_shift);
+            }
+            cell &= max(W::NUM_BITS);
+
+            if dst_shift == 0 {
+                dst.push(W::from_u64(cell));
+            } else {
+                let last = dst.len() - 1;
+                let low = dst[last].into() & max(dst_shift);
+                dst[last] = W::from_u64(low | (cell << dst_shift));
+                dst.push(W::from_u64(cell >> (W::NUM_BITS - dst_shift)));
+            }
+        }
+    }
+
+    dst.truncate(total_bits.div_ceil(W::NUM_BITS));
+    let end_bit = total_bits % W::NUM_BITS;
+    if end_bit > 0 {
+        let last = dst.len() - 1;
+        dst[last] = W::from_u64(dst[last].into() & max(end_bit));
+    }
+}
+
+/// Copies the `num_bits` bits starting at bit `src_bit` to bit `dst_bit`, like `memmove`. The
+/// two ranges may overlap.
+pub(crate) fn copy_bits_within<W: Word>(
+    buf: &mut [W],
+    src_bit: usize,
+    dst_bit: usize,
+    num_bits: usize,
+) {
+    // Move 64 bits at a time, working away from the side the bits are moving towards so that
+    // no bit is overwritten before it has been read.
+    if dst_bit < src_bit {
+        let mut copied = 0;
+        while copied < num_bits {
+            let chunk = (num_bits - copied).min(64);
+            let value = read(buf, src_bit + copied, chunk);
+            write(buf, dst_bit + copied, chunk, value);
+            copied += chunk;
+        }
+    } else if dst_bit > src_bit {
+        let mut remaining = num_bits;
+        while remaining > 0 {
+            let chunk = remaining.min(64);
+            remaining -= chunk;
+            let value = read(buf, src_bit + remaining, chunk);
+            write(buf, dst_bit + remaining, chunk, value);
+        }
+    }
+}
+
+/// Returns the cells of a buffer filled with `num_bits`-bit copies of `value`, up to the point
+/// where the cells start to repeat. This is `num_bits / gcd(num_bits, W::NUM_BITS)` cells.
+pub(crate) fn fill_pattern<W: Word>(num_bits: usize, value: u64) -> Vec<W> {
+    let (mut a, mut b) = (num_bits, W::NUM_BITS);
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+
+    let mut pattern = vec![W::default(); num_bits / a];
+    for i in 0..pattern.len() * W::NUM_BITS / num_bits {
+        write(&mut pattern, i * num_bits, num_bits, value);
+    }
+    pattern
+}
+
+/// Sets bits `start_bit..end_bit` to those of `pattern` repeated endlessly from bit 0. Whole
+/// cells are copied from the pattern, and only the cells at either end are masked.
+pub(crate) fn fill_bits<W: Word>(buf: &mut [W], start_bit: usize, end_bit: usize, pattern: &[W]) {
+    let pattern_at = |bit: usize| pattern[bit / W::NUM_BITS % pattern.len()].into();
+    let first_cell = start_bit.div_ceil(W::NUM_BITS);
+    let end_cell = end_bit / W::NUM_BITS;
+
+    if first_cell > end_cell {
+        // The range lies within a single cell.
+        let num_bits = end_bit - start_bit;
+        if num_bits > 0 {
+            let value = pattern_at(start_bit) >> (start_bit % W::NUM_BITS);
+            write(buf, start_bit, num_bits, value & max(num_bits));
+        }
+        return;
+    }
+
+    let head_bits = first_cell * W::NUM_BITS - start_bit;
+    if head_bits > 0 {
+        let value = pattern_at(start_bit) >> (start_bit % W::NUM_BITS);
+        write(buf, start_bit, head_bits, value & max(head_bits));
+    }
+    for (i, cell) in buf[first_cell..end_cell].iter_mut().enumerate() {
+        *cell = pattern[(first_cell + i) % pattern.len()];
+    }
+    let tail_bits = end_bit - end_cell * W::NUM_BITS;
+    if tail_bits > 0 {
+        let value = pattern_at(end_cell * W::NUM_BITS);
+        write(
+            buf,
+            end_cell * W::NUM_BITS,
+            tail_bits,
+            value & max(tail_bits),
+        );
+    }
+}
+
+/// Returns a copy of the first `num_bits` bits of `buf`, with any bits past the end cleared.
+pub(crate) fn to_vec<W: Word>(buf: &[W], num_bits: usize) -> Vec<W> {
+    let buf_index = num_bits / W::NUM_BITS;
+    let start_bit = num_bits % W::NUM_BITS;
+
+    // Certain functions "remove" integers by setting the struct's `len` value. A side effect
+    // of this optimisation is that the actual contents of the backing vector may not be what
+    // is expected. Ensure that any lingering integers are cleaned up prior to returning this
+    // copy.
+    let mut vec;
+    if start_bit > 0 {
+        vec = buf[0..=buf_index].to_vec();
+        vec[buf_index] = W::from_u64(vec[buf_index].into() & !(u64::MAX << start_bit));
+    } else {
+        vec = buf[0..buf_index].to_vec();
+    }
+    vec
+}
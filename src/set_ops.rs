@@ -0,0 +1,226 @@
+//! Set operations on sorted `PackedIntegers`. Each input is treated as the set of its distinct
+//! integers, and results are sorted with no duplicates.
+
+use crate::{PackedInt, PackedIntegers, Word};
+
+/// Which integers a set operation keeps: those only in the left set, those in both, and those
+/// only in the right set.
+#[derive(Clone, Copy)]
+struct SetOp {
+    left: bool,
+    both: bool,
+    right: bool,
+}
+
+const DIFFERENCE: SetOp = SetOp {
+    left: true,
+    both: false,
+    right: false,
+};
+const INTERSECTION: SetOp = SetOp {
+    left: false,
+    both: true,
+    right: false,
+};
+const SYMMETRIC_DIFFERENCE: SetOp = SetOp {
+    left: true,
+    both: false,
+    right: true,
+};
+const UNION: SetOp = SetOp {
+    left: true,
+    both: true,
+    right: true,
+};
+
+impl<T: PackedInt, W: Word> PackedIntegers<T, W> {
+    /// Returns the distinct integers of a sorted vector that are not in the sorted vector
+    /// `other`, in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let a = packed_ints![1, 2, 2, 3, 5; U9];
+    /// let b = packed_ints![2, 4, 5; U9];
+    ///
+    /// assert_eq!(a.difference(&b), packed_ints![1, 3; U9]);
+    /// ```
+    pub fn difference(&self, other: &Self) -> PackedIntegers<T, W> {
+        self.collect_set_op(other, DIFFERENCE)
+    }
+
+    /// Returns the number of integers `difference` would return, without building them.
+    pub fn difference_count(&self, other: &Self) -> usize {
+        self.count_set_op(other, DIFFERENCE)
+    }
+
+    /// Returns the distinct integers in both a sorted vector and the sorted vector `other`, in
+    /// ascending order.
+    ///
+    /// Runs of integers with no match in the other vector are skipped with a galloping search, so
+    /// intersecting a short vector with a long one takes time roughly proportional to the short
+    /// vector's length times the log of the long one's.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let a = packed_ints![1, 2, 2, 3, 5; U9];
+    /// let b = packed_ints![2, 4, 5; U9];
+    ///
+    /// assert_eq!(a.intersection(&b), packed_ints![2, 5; U9]);
+    /// ```
+    pub fn intersection(&self, other: &Self) -> PackedIntegers<T, W> {
+        self.collect_set_op(other, INTERSECTION)
+    }
+
+    /// Returns the number of integers `intersection` would return, without building them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{PackedIntegers, U20};
+    ///
+    /// let a: PackedIntegers<U20> = (0..1_000_000).step_by(3).collect();
+    /// let b: PackedIntegers<U20> = (0..1_000_000).step_by(5).collect();
+    ///
+    /// assert_eq!(a.intersection_count(&b), 66_667);
+    /// ```
+    pub fn intersection_count(&self, other: &Self) -> usize {
+        self.count_set_op(other, INTERSECTION)
+    }
+
+    /// Returns the distinct integers in exactly one of a sorted vector and the sorted vector
+    /// `other`, in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let a = packed_ints![1, 2, 2, 3, 5; U9];
+    /// let b = packed_ints![2, 4, 5; U9];
+    ///
+    /// assert_eq!(a.symmetric_difference(&b), packed_ints![1, 3, 4; U9]);
+    /// ```
+    pub fn symmetric_difference(&self, other: &Self) -> PackedIntegers<T, W> {
+        self.collect_set_op(other, SYMMETRIC_DIFFERENCE)
+    }
+
+    /// Returns the number of integers `symmetric_difference` would return, without building
+    /// them.
+    pub fn symmetric_difference_count(&self, other: &Self) -> usize {
+        self.count_set_op(other, SYMMETRIC_DIFFERENCE)
+    }
+
+    /// Returns the distinct integers in either a sorted vector or the sorted vector `other`, in
+    /// ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use packed_integers::{packed_ints, U9};
+    ///
+    /// let a = packed_ints![1, 2, 2, 3, 5; U9];
+    /// let b = packed_ints![2, 4, 5; U9];
+    ///
+    /// assert_eq!(a.union(&b), packed_ints![1, 2, 3, 4, 5; U9]);
+    /// ```
+    pub fn union(&self, other: &Self) -> PackedIntegers<T, W> {
+        self.collect_set_op(other, UNION)
+    }
+
+    /// Returns the number of integers `union` would return, without building them.
+    pub fn union_count(&self, other: &Self) -> usize {
+        self.count_set_op(other, UNION)
+    }
+
+    fn collect_set_op(&self, other: &Self, op: SetOp) -> PackedIntegers<T, W> {
+        let mut result = PackedIntegers::new();
+        self.set_op(other, op, |value| result.push(value));
+        result
+    }
+
+    fn count_set_op(&self, other: &Self, op: SetOp) -> usize {
+        let mut count = 0;
+        self.set_op(other, op, |_| count += 1);
+        count
+    }
+
+    /// Merges two sorted vectors, passing each distinct integer that `op` keeps to `emit` in
+    /// ascending order.
+    fn set_op<F: FnMut(T::Value)>(&self, other: &Self, op: SetOp, mut emit: F) {
+        let (mut i, mut j) = (0, 0);
+        while i < self.len && j < other.len {
+            let x = self.get_unchecked(i);
+            let y = other.get_unchecked(j);
+            if x < y {
+                let end = self.gallop(i, |v| v < y);
+                if op.left {
+                    self.emit_distinct(i, end, &mut emit);
+                }
+                i = end;
+            } else if y < x {
+                let end = other.gallop(j, |v| v < x);
+                if op.right {
+                    other.emit_distinct(j, end, &mut emit);
+                }
+                j = end;
+            } else {
+                if op.both {
+                    emit(x);
+                }
+                i = self.gallop(i, |v| v <= x);
+                j = other.gallop(j, |v| v <= x);
+            }
+        }
+
+        if op.left {
+            self.emit_distinct(i, self.len, &mut emit);
+        }
+        if op.right {
+            other.emit_distinct(j, other.len, &mut emit);
+        }
+    }
+
+    /// Passes each distinct integer in `start..end` of a sorted vector to `emit`.
+    fn emit_distinct<F: FnMut(T::Value)>(&self, start: usize, end: usize, emit: &mut F) {
+        let mut last = None;
+        for i in start..end {
+            let value = self.get_unchecked(i);
+            if last != Some(value) {
+                emit(value);
+                last = Some(value);
+            }
+        }
+    }
+
+    /// Returns the position of the first integer at or after `start` for which `pred` returns
+    /// `false`, given that `pred` is `true` for every integer before it. The search probes
+    /// positions `start`, `start + 1`, `start + 2`, `start + 4` and so on, then binary searches
+    /// the last step, so it is fast when the answer is close to `start`.
+    fn gallop<P: Fn(T::Value) -> bool>(&self, start: usize, pred: P) -> usize {
+        let mut low = start;
+        let mut step = 1;
+        let mut high = start;
+        while high < self.len && pred(self.get_unchecked(high)) {
+            low = high + 1;
+            high = start + step;
+            step *= 2;
+        }
+
+        let mut high = high.min(self.len);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if pred(self.get_unchecked(mid)) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+}
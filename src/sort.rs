@@ -0,0 +1,207 @@
+//! Sorting routines that work directly on packed buffers. Positions are integer indices into a
+//! buffer of `num_bits`-bit integers.
+
+use crate::{raw, Word};
+
+/// Ranges at most this long are insertion sorted.
+const INSERTION_SORT_LEN: usize = 32;
+
+/// The number of key bits each radix sort pass distributes on.
+const RADIX_BITS: usize = 8;
+
+#[inline]
+fn get<W: Word>(buf: &[W], num_bits: usize, index: usize) -> u64 {
+    raw::read(buf, index * num_bits, num_bits)
+}
+
+#[inline]
+fn set<W: Word>(buf: &mut [W], num_bits: usize, index: usize, value: u64) {
+    raw::write(buf, index * num_bits, num_bits, value)
+}
+
+/// Stably sorts the integers in `start..end` by `key`. Each integer's position is found before
+/// anything is moved, so a panic in `key` leaves the integers in some order, with none lost.
+pub(crate) fn insertion_sort<W, K, F>(
+    buf: &mut [W],
+    num_bits: usize,
+    start: usize,
+    end: usize,
+    key: &mut F,
+) where
+    W: Word,
+    K: Ord,
+    F: FnMut(u64) -> K,
+{
+    for i in (start + 1)..end {
+        let value = get(buf, num_bits, i);
+        let value_key = key(value);
+
+        let mut j = i;
+        while j > start && key(get(buf, num_bits, j - 1)) > value_key {
+            j -= 1;
+        }
+        if j != i {
+            raw::copy_bits_within(buf, j * num_bits, (j + 1) * num_bits, (i - j) * num_bits);
+            set(buf, num_bits, j, value);
+        }
+    }
+}
+
+/// Sorts the integers in `start..end`, which agree on all but their low `key_bits` bits, with an
+/// in-place MSD radix sort (American flag sort).
+///
+/// Each pass counts the integers in each bucket of the next `RADIX_BITS` bits, then moves every
+/// integer straight into its bucket by following cycles of displaced integers. Buckets are then
+/// sorted recursively on the remaining bits.
+pub(crate) fn radix_sort<W: Word>(
+    buf: &mut [W],
+    num_bits: usize,
+    start: usize,
+    end: usize,
+    key_bits: usize,
+) {
+    if end - start <= INSERTION_SORT_LEN {
+        insertion_sort(buf, num_bits, start, end, &mut |value| value);
+        return;
+    }
+
+    let digit_bits = key_bits.min(RADIX_BITS);
+    let shift = key_bits - digit_bits;
+    let num_buckets = 1 << digit_bits;
+    let digit = |value: u64| (value >> shift) as usize & (num_buckets - 1);
+
+    let mut counts = [0; 1 << RADIX_BITS];
+    for i in start..end {
+        counts[digit(get(buf, num_bits, i))] += 1;
+    }
+
+    // `heads[d]` is the next unsorted position of bucket `d`, and `tails[d]` its end.
+    let mut heads = [0; 1 << RADIX_BITS];
+    let mut tails = [0; 1 << RADIX_BITS];
+    let mut position = start;
+    for d in 0..num_buckets {
+        heads[d] = position;
+        position += counts[d];
+        tails[d] = position;
+    }
+
+    for d in 0..num_buckets {
+        while heads[d] < tails[d] {
+            let mut value = get(buf, num_bits, heads[d]);
+            let mut value_digit = digit(value);
+            while value_digit != d {
+                let slot = heads[value_digit];
+                heads[value_digit] += 1;
+                let displaced = get(buf, num_bits, slot);
+                set(buf, num_bits, slot, value);
+                value = displaced;
+                value_digit = digit(value);
+            }
+            set(buf, num_bits, heads[d], value);
+            heads[d] += 1;
+        }
+    }
+
+    if shift > 0 {
+        let mut bucket_start = start;
+        for &bucket_end in &tails[..num_buckets] {
+            if bucket_end - bucket_start > 1 {
+                radix_sort(buf, num_bits, bucket_start, bucket_end, shift);
+            }
+            bucket_start = bucket_end;
+        }
+    }
+}
+
+/// Stably sorts the first `len` integers by `key`, with a bottom-up merge sort. The scratch space
+/// is a packed buffer of the same size.
+///
+/// Each merge pass only reads from its source, so if `key` panics the source still holds every
+/// integer, and is copied back to `buf` if it was the scratch buffer.
+pub(crate) fn merge_sort<W, K, F>(buf: &mut [W], num_bits: usize, len: usize, mut key: F)
+where
+    W: Word,
+    K: Ord,
+    F: FnMut(u64) -> K,
+{
+    for start in (0..len).step_by(INSERTION_SORT_LEN) {
+        let end = (start + INSERTION_SORT_LEN).min(len);
+        insertion_sort(buf, num_bits, start, end, &mut key);
+    }
+    if len <= INSERTION_SORT_LEN {
+        return;
+    }
+
+    let mut state = MergeState {
+        scratch: vec![W::default(); buf.len()],
+        buf,
+        in_scratch: false,
+    };
+    let mut width = INSERTION_SORT_LEN;
+    while width < len {
+        let (src, dst) = if state.in_scratch {
+            (&state.scratch[..], &mut *state.buf)
+        } else {
+            (&*state.buf, &mut state.scratch[..])
+        };
+        for start in (0..len).step_by(2 * width) {
+            let mid = (start + width).min(len);
+            let end = (start + 2 * width).min(len);
+            merge(src, dst, num_bits, start, mid, end, &mut key);
+        }
+        state.in_scratch = !state.in_scratch;
+        width *= 2;
+    }
+}
+
+/// The buffers of a merge sort. Whichever holds the integers is copied back to `buf` on drop,
+/// whether the sort finished or `key` panicked.
+struct MergeState<'a, W: Word> {
+    buf: &'a mut [W],
+    scratch: Vec<W>,
+    in_scratch: bool,
+}
+
+impl<W: Word> Drop for MergeState<'_, W> {
+    fn drop(&mut self) {
+        if self.in_scratch {
+            self.buf.copy_from_slice(&self.scratch);
+        }
+    }
+}
+
+/// Merges the sorted runs `start..mid` and `mid..end` of `src` into the same positions of `dst`,
+/// taking from the first run when keys are equal.
+fn merge<W, K, F>(
+    src: &[W],
+    dst: &mut [W],
+    num_bits: usize,
+    start: usize,
+    mid: usize,
+    end: usize,
+    key: &mut F,
+) where
+    W: Word,
+    K: Ord,
+    F: FnMut(u64) -> K,
+{
+    let (mut i, mut j) = (start, mid);
+    for k in start..end {
+        let take_left = if i == mid {
+            false
+        } else if j == end {
+            true
+        } else {
+            key(get(src, num_bits, i)) <= key(get(src, num_bits, j))
+        };
+
+        let value = if take_left {
+            i += 1;
+            get(src, num_bits, i - 1)
+        } else {
+            j += 1;
+            get(src, num_bits, j - 1)
+        };
+        set(dst, num_bits, k, value);
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt::Debug;
+
+/// A trait for the unsigned integer types that can back a `PackedIntegers` buffer.
+///
+/// Wider words mean fewer integers spanning multiple buffer cells, while `u8` words give a
+/// byte-exact layout with no trailing padding.
+///
+/// This trait is sealed: the bit-level code relies on words being 8, 16, 32 or 64 bits wide, so
+/// it is implemented for `u8`, `u16`, `u32` and `u64` only.
+///
+/// ```compile_fail
+/// use packed_integers::Word;
+///
+/// #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// struct U12(u16);
+///
+/// impl From<U12> for u64 {
+///     fn from(word: U12) -> u64 {
+///         word.0 as u64
+///     }
+/// }
+///
+/// impl Word for U12 {
+///     const NUM_BITS: usize = 12;
+///
+///     fn from_u64(value: u64) -> U12 {
+///         U12(value as u16 & 0xfff)
+///     }
+/// }
+/// ```
+pub trait Word: Copy + Debug + Default + Eq + Into<u64> + private::Sealed {
+    /// The number of bits in this word type.
+    const NUM_BITS: usize;
+
+    /// Converts a `u64` into this type, discarding any high bits that do not fit.
+    fn from_u64(value: u64) -> Self;
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+macro_rules! word {
+    ($type:ty) => {
+        impl private::Sealed for $type {}
+
+        impl Word for $type {
+            const NUM_BITS: usize = <$type>::BITS as usize;
+
+            #[inline]
+            fn from_u64(value: u64) -> $type {
+                value as $type
+            }
+        }
+    };
+}
+
+word!(u8);
+word!(u16);
+word!(u32);
+word!(u64);
@@ -0,0 +1,90 @@
+use packed_integers::*;
+
+fn delta<T: PackedInt>(sample_rate: usize, values: &[u64]) -> DeltaPackedIntegers<T> {
+    let mut v = DeltaPackedIntegers::with_sample_rate(sample_rate);
+    for &value in values {
+        v.push(value).unwrap();
+    }
+    v
+}
+
+#[test]
+fn get() {
+    let values: Vec<u64> = (0..100).map(|i| 1_000_000 + i * i).collect();
+    let v = delta::<U8>(8, &values);
+
+    for (i, &value) in values.iter().enumerate() {
+        assert_eq!(v.get(i), Some(value));
+    }
+    assert_eq!(v.get(100), None);
+}
+
+#[test]
+fn get_sample_rate_one() {
+    let v = delta::<U1>(1, &[5, 500, 50000]);
+
+    assert_eq!(v.gaps(), &packed_ints![0, 0, 0; U1]);
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![5, 500, 50000]);
+}
+
+#[test]
+fn iter() {
+    let values: Vec<u64> = (0..100).map(|i| (1 << 40) + i * 3).collect();
+    let v = delta::<U2>(7, &values);
+
+    assert_eq!(v.iter().collect::<Vec<_>>(), values);
+}
+
+#[test]
+fn pop() {
+    let mut v = delta::<U8>(2, &[10, 20, 30]);
+
+    assert_eq!(v.pop(), Some(30));
+    assert_eq!(v.pop(), Some(20));
+    v.push(15).unwrap();
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![10, 15]);
+    assert_eq!(v.pop(), Some(15));
+    assert_eq!(v.pop(), Some(10));
+    assert_eq!(v.pop(), None);
+}
+
+#[test]
+fn push_equal() {
+    let v = delta::<U1>(4, &[7, 7, 7, 8]);
+
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![7, 7, 7, 8]);
+}
+
+#[test]
+#[should_panic]
+fn push_gap_gt_max() {
+    let mut v = DeltaPackedIntegers::<U4>::new();
+    v.push(0).unwrap();
+    let _ = v.push(16);
+}
+
+#[test]
+fn push_regression() {
+    let mut v = delta::<U8>(2, &[10, 20]);
+
+    // Regressions are rejected at sampled positions too.
+    assert_eq!(
+        v.push(19),
+        Err(UnsortedError {
+            index: 2,
+            value: 19,
+            last: 20
+        })
+    );
+    assert_eq!(v.len(), 2);
+}
+
+#[test]
+fn truncate() {
+    let mut v = delta::<U8>(4, &[1, 2, 3, 4, 5, 6]);
+    v.truncate(4);
+
+    assert_eq!(v.last(), Some(4));
+    v.push(10).unwrap();
+    assert_eq!(v, delta::<U8>(3, &[1, 2, 3, 4, 10]));
+}
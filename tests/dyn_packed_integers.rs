@@ -0,0 +1,176 @@
+#![allow(clippy::unusual_byte_groupings)]
+
+use packed_integers::*;
+use std::convert::TryFrom;
+
+#[test]
+fn append() {
+    let mut v1 = DynPackedIntegers::from_slice(9, &[1, 2]);
+    let mut v2 = DynPackedIntegers::from_slice(9, &[3, 4, 5]);
+    v1.append(&mut v2);
+
+    assert_eq!(v1, DynPackedIntegers::from_slice(9, &[1, 2, 3, 4, 5]));
+    assert!(v2.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn append_width_mismatch() {
+    let mut v1 = DynPackedIntegers::from_slice(9, &[1, 2]);
+    let mut v2 = DynPackedIntegers::from_slice(10, &[3, 4, 5]);
+    v1.append(&mut v2);
+}
+
+#[test]
+fn eq_width_mismatch() {
+    let v1 = DynPackedIntegers::from_slice(9, &[1, 2]);
+    let v2 = DynPackedIntegers::from_slice(10, &[1, 2]);
+
+    assert_ne!(v1, v2);
+}
+
+#[test]
+fn from_packed() {
+    let v = DynPackedIntegers::from(packed_ints![507, 508, 509, 510, 511; U9]);
+
+    assert_eq!(v.num_bits(), 9);
+    assert_eq!(
+        v,
+        DynPackedIntegers::from_slice(9, &[507, 508, 509, 510, 511])
+    );
+}
+
+#[test]
+fn from_vec_u9_eq() {
+    let b = vec![
+        0b10011_111111101_111111100_111111011,
+        0b111111111_111111110_1100,
+    ];
+    let v = DynPackedIntegers::from_vec(9, b, 6);
+
+    assert_eq!(v.get(0), Some(0b111111011));
+    assert_eq!(v.get(3), Some(0b110010011));
+    assert_eq!(v.get(5), Some(0b111111111));
+    assert_eq!(v.get(6), None);
+}
+
+#[test]
+#[should_panic]
+fn from_vec_u8_gt() {
+    let b = vec![0b11111110_11111101_11111100_11111011];
+    let _v = DynPackedIntegers::from_vec(8, b, 5);
+}
+
+#[test]
+fn get_u40_span() {
+    let values = [(1 << 40) - 1, 1, 1 << 39, 0x12_3456_789a, 0];
+    let v = DynPackedIntegers::from_slice(40, &values);
+
+    assert_eq!(v.len(), 5);
+    for (i, &value) in values.iter().enumerate() {
+        assert_eq!(v.get(i), Some(value));
+    }
+    assert_eq!(v.get(5), None);
+}
+
+#[test]
+fn insert_remove() {
+    let mut v = DynPackedIntegers::from_slice(9, &[1, 2, 3]);
+    v.insert(0, 4);
+    v.insert(4, 5);
+
+    assert_eq!(v, DynPackedIntegers::from_slice(9, &[4, 1, 2, 3, 5]));
+
+    assert_eq!(v.remove(1), 1);
+    assert_eq!(v, DynPackedIntegers::from_slice(9, &[4, 2, 3, 5]));
+}
+
+#[test]
+fn into_iter() {
+    let v = DynPackedIntegers::from_slice(33, &[1 << 32, 2, 3]);
+
+    assert_eq!(v.into_iter().collect::<Vec<_>>(), vec![1 << 32, 2, 3]);
+}
+
+#[test]
+#[should_panic]
+fn new_zero_bits() {
+    let _v = DynPackedIntegers::new(0);
+}
+
+#[test]
+#[should_panic]
+fn new_65_bits() {
+    let _v = DynPackedIntegers::new(65);
+}
+
+#[test]
+#[should_panic]
+fn push_gt_max() {
+    let mut v = DynPackedIntegers::new(10);
+    v.push(1024);
+}
+
+#[test]
+fn to_vec_matches_packed() {
+    let p = packed_ints![100, 200, 300, 400, 500; U9];
+    let v = DynPackedIntegers::from_slice(9, &[100, 200, 300, 400, 500]);
+
+    assert_eq!(v.to_vec(), p.to_vec());
+}
+
+#[test]
+fn try_into_packed() {
+    let v = DynPackedIntegers::from_slice(9, &[1, 2, 3]);
+
+    let v = PackedIntegers::<U8>::try_from(v).unwrap_err();
+    let p = PackedIntegers::<U9>::try_from(v).unwrap();
+    assert_eq!(p, packed_ints![1, 2, 3; U9]);
+}
+
+#[test]
+fn from_slice_auto() {
+    let v = DynPackedIntegers::from_slice_auto(&[507u32, 508, 509, 510, 511]);
+
+    assert_eq!(v.num_bits(), 9);
+    assert_eq!(
+        v.to_vec(),
+        packed_ints![507, 508, 509, 510, 511; U9].to_vec()
+    );
+}
+
+#[test]
+fn from_slice_auto_empty() {
+    let v = DynPackedIntegers::from_slice_auto::<u32>(&[]);
+
+    assert_eq!(v.num_bits(), 1);
+    assert!(v.is_empty());
+}
+
+#[test]
+fn from_slice_auto_u32_max() {
+    let v = DynPackedIntegers::from_slice_auto(&[0, u32::MAX]);
+
+    assert_eq!(v.num_bits(), 32);
+    assert_eq!(v.get(1), Some(u64::from(u32::MAX)));
+}
+
+#[test]
+fn from_slice_auto_u64() {
+    let v = DynPackedIntegers::from_slice_auto(&[1u64 << 50, 7, u64::MAX >> 14]);
+
+    assert_eq!(v.num_bits(), 51);
+    assert_eq!(
+        v.iter().collect::<Vec<_>>(),
+        vec![1 << 50, 7, u64::MAX >> 14]
+    );
+}
+
+#[test]
+fn from_slice_auto_zeroes() {
+    let v = DynPackedIntegers::from_slice_auto(&[0u32, 0, 0]);
+
+    assert_eq!(v.num_bits(), 1);
+    assert_eq!(v.len(), 3);
+    assert_eq!(v.get(2), Some(0));
+}
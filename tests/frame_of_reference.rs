@@ -0,0 +1,130 @@
+use packed_integers::*;
+
+#[test]
+fn from_slice() {
+    let v = FrameOfReference::<U9>::from_slice(&[1_000_300, 1_000_000, 1_000_511]);
+
+    assert_eq!(v.base(), 1_000_000);
+    assert_eq!(v.offsets(), &packed_ints![300, 0, 511; U9]);
+    assert_eq!(
+        v.iter().collect::<Vec<_>>(),
+        vec![1_000_300, 1_000_000, 1_000_511]
+    );
+}
+
+#[test]
+#[should_panic]
+fn from_slice_range_too_wide() {
+    let _v = FrameOfReference::<U9>::from_slice(&[1_000_000, 1_000_512]);
+}
+
+#[test]
+fn eq_ignores_base() {
+    let mut v1 = FrameOfReference::<U9>::new(100);
+    v1.push(150);
+    let mut v2 = FrameOfReference::<U9>::new(50);
+    v2.push(150);
+
+    assert_eq!(v1, v2);
+}
+
+#[test]
+fn pop() {
+    let mut v = FrameOfReference::<U9>::from_slice(&[1_000_300, 1_000_000]);
+
+    assert_eq!(v.pop(), Some(1_000_000));
+    assert_eq!(v.pop(), Some(1_000_300));
+    assert_eq!(v.pop(), None);
+}
+
+#[test]
+fn push_below_base_rebases() {
+    let mut v = FrameOfReference::<U9>::new(1_000_000);
+    v.push(1_000_100);
+    v.push(1_000_000);
+    v.push(999_800);
+
+    assert_eq!(v.base(), 999_800);
+    assert_eq!(v.offsets(), &packed_ints![300, 200, 0; U9]);
+}
+
+#[test]
+#[should_panic]
+fn push_below_base_too_far() {
+    let mut v = FrameOfReference::<U9>::new(1_000_000);
+    v.push(1_000_100);
+    v.push(999_000);
+}
+
+#[test]
+#[should_panic]
+fn push_gt_max() {
+    let mut v = FrameOfReference::<U9>::new(1_000_000);
+    v.push(1_000_512);
+}
+
+#[test]
+fn push_u64_base() {
+    let base = 1 << 60;
+    let mut v = FrameOfReference::<U20>::new(base);
+    v.push(base + 12345);
+    v.push(base + (1 << 20) - 1);
+
+    assert_eq!(v.get(0), Some(base + 12345));
+    assert_eq!(v.get(1), Some(base + (1 << 20) - 1));
+}
+
+#[test]
+fn rebase_up() {
+    let mut v = FrameOfReference::<U9>::from_slice(&[1_000_100, 1_000_200]);
+    v.rebase(1_000_000);
+    v.rebase(1_000_100);
+
+    assert_eq!(v.base(), 1_000_100);
+    assert_eq!(v.offsets(), &packed_ints![0, 100; U9]);
+}
+
+#[test]
+#[should_panic]
+fn rebase_above_min() {
+    let mut v = FrameOfReference::<U9>::from_slice(&[1_000_100, 1_000_200]);
+    v.rebase(1_000_101);
+}
+
+#[test]
+fn set_rebases() {
+    let mut v = FrameOfReference::<U9>::from_slice(&[500, 600, 700]);
+    v.set(1, 400);
+
+    assert_eq!(v.base(), 400);
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![500, 400, 700]);
+}
+
+#[test]
+fn set_rebases_over_replaced_value() {
+    // Only 100 remains once 600 is replaced, so a base of 50 fits.
+    let mut v = FrameOfReference::<U9>::from_slice(&[100, 600]);
+    v.set(1, 50);
+
+    assert_eq!(v.base(), 50);
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![100, 50]);
+}
+
+#[test]
+#[should_panic]
+fn set_below_base_too_far() {
+    let mut v = FrameOfReference::<U9>::from_slice(&[100, 600, 300]);
+    v.set(0, 50);
+}
+
+#[test]
+fn u64_max_base() {
+    let mut v = FrameOfReference::<U9>::new(u64::MAX - 5);
+    v.push(u64::MAX);
+    v.push(u64::MAX - 300);
+
+    assert_eq!(v.base(), u64::MAX - 300);
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![u64::MAX, u64::MAX - 300]);
+    assert_eq!(v.pop(), Some(u64::MAX - 300));
+    assert_eq!(v.get(0), Some(u64::MAX));
+}
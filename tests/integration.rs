@@ -1,3 +1,5 @@
+#![allow(clippy::unusual_byte_groupings)]
+
 use packed_integers::*;
 use std::cmp::Ordering;
 
@@ -10,6 +12,17 @@ fn append() {
     assert_eq!(v1, packed_ints![1, 2, 3, 4, 5; U9]);
 }
 
+#[test]
+fn append_after_truncate() {
+    let mut v = packed_ints![511, 511, 511, 511; U9];
+    v.truncate(1);
+    let mut w = packed_ints![0, 0; U9];
+    v.append(&mut w);
+
+    assert_eq!(v, packed_ints![511, 0, 0; U9]);
+    assert_eq!(v.to_vec(), packed_ints![511, 0, 0; U9].to_vec());
+}
+
 #[test]
 fn append_empty() {
     let mut v1 = packed_ints![1, 2; U8];
@@ -19,6 +32,97 @@ fn append_empty() {
     assert_eq!(v1, packed_ints![1, 2; U8]);
 }
 
+#[test]
+fn append_unaligned() {
+    // `v` ends 27 bits into its first cell, so every cell of `w` is shifted across a boundary.
+    let mut v = packed_ints![1, 2, 3; U9];
+    let mut w = packed_ints![511, 4, 5, 6, 7; U9];
+    v.append(&mut w);
+
+    assert_eq!(v, packed_ints![1, 2, 3, 511, 4, 5, 6, 7; U9]);
+    assert_eq!(
+        v.to_vec(),
+        packed_ints![1, 2, 3, 511, 4, 5, 6, 7; U9].to_vec()
+    );
+    assert!(w.is_empty());
+}
+
+#[test]
+fn append_word_aligned() {
+    let mut v = packed_ints![1, 2, 3, 4; U8];
+    let mut w = packed_ints![5, 6, 7; U8];
+    v.append(&mut w);
+
+    assert_eq!(v.to_vec(), vec![0x04030201, 0x00070605]);
+    assert!(w.is_empty());
+}
+
+#[test]
+fn binary_search_absent() {
+    let v = packed_ints![100, 200, 300; U9];
+
+    assert_eq!(v.binary_search(50), Err(0));
+    assert_eq!(v.binary_search(250), Err(2));
+    assert_eq!(v.binary_search(511), Err(3));
+    assert!(!v.contains_sorted(250));
+}
+
+#[test]
+fn binary_search_duplicates() {
+    let v = packed_ints![100, 200, 200, 200, 300; U9];
+
+    assert_eq!(v.binary_search(200), Ok(1));
+    assert_eq!(v.lower_bound(200), 1);
+    assert_eq!(v.upper_bound(200), 4);
+    assert_eq!(v.partition_point(|&x| x <= 200), 4);
+    assert!(v.contains_sorted(200));
+}
+
+#[test]
+fn binary_search_empty() {
+    let v = PackedIntegers::<U9>::new();
+
+    assert_eq!(v.binary_search(1), Err(0));
+    assert_eq!(v.lower_bound(1), 0);
+    assert_eq!(v.upper_bound(1), 0);
+    assert!(!v.contains_sorted(0));
+}
+
+#[test]
+fn binary_search_first_last() {
+    let v = packed_ints![0, 100, 200, 511; U9];
+
+    assert_eq!(v.binary_search(0), Ok(0));
+    assert_eq!(v.binary_search(511), Ok(3));
+    assert_eq!(v.lower_bound(0), 0);
+    assert_eq!(v.upper_bound(0), 1);
+    assert_eq!(v.lower_bound(511), 3);
+    assert_eq!(v.upper_bound(511), 4);
+}
+
+#[test]
+fn bits_alias() {
+    let mut v1 = packed_ints![1, 2, 3; Bits<9>];
+    let v2: PackedIntegers<U9> = packed_ints![1, 2, 3; U9];
+    assert_eq!(v1, v2);
+
+    v1.push(Bits::<9>::MAX);
+    assert_eq!(v1.get(3), Some(511));
+}
+
+#[test]
+fn bits_const_width() {
+    const fn bits_for(max: u64) -> usize {
+        (u64::BITS - max.leading_zeros()) as usize
+    }
+
+    let mut v = PackedIntegers::<Bits<{ bits_for(1 << 40) }>>::new();
+    v.push(1 << 40);
+
+    assert_eq!(Bits::<{ bits_for(1 << 40) }>::NUM_BITS, 41);
+    assert_eq!(v.get(0), Some(1 << 40));
+}
+
 #[test]
 fn clear() {
     let mut v = packed_ints![251, 252, 253, 254, 255; U8];
@@ -38,6 +142,205 @@ fn clone() {
     assert_eq!(v2, packed_ints![251, 252, 253, 254, 255; U8]);
 }
 
+#[test]
+fn collect() {
+    let v: PackedIntegers<U9> = (500..512).collect();
+    let mut pushed = PackedIntegers::<U9>::new();
+    for i in 500..512 {
+        pushed.push(i);
+    }
+
+    assert_eq!(v, pushed);
+    assert_eq!(v.to_vec(), pushed.to_vec());
+}
+
+#[test]
+fn dedup() {
+    let mut v = packed_ints![1, 1, 2, 3, 3, 3, 1, 511, 511; U9];
+    v.dedup();
+
+    assert_eq!(v, packed_ints![1, 2, 3, 1, 511; U9]);
+    assert_eq!(v.to_vec(), packed_ints![1, 2, 3, 1, 511; U9].to_vec());
+}
+
+#[test]
+fn dedup_all_equal() {
+    let mut v = packed_ints![7, 7, 7, 7, 7; U9];
+    v.dedup();
+
+    assert_eq!(v, packed_ints![7; U9]);
+}
+
+#[test]
+fn dedup_by_key() {
+    let mut v = packed_ints![1, 3, 5, 2, 4, 7, 9, 8; U9];
+    v.dedup_by_key(|v| *v % 2);
+
+    assert_eq!(v, packed_ints![1, 2, 7, 8; U9]);
+}
+
+#[test]
+fn dedup_by_panic() {
+    let mut v = packed_ints![1, 1, 2, 2, 3, 3; U9];
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        v.dedup_by(|a, b| {
+            if *a == 3 {
+                panic!("closure panicked");
+            }
+            a == b
+        })
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(v, packed_ints![1, 2, 3, 3; U9]);
+}
+
+#[test]
+fn dedup_empty() {
+    let mut v = PackedIntegers::<U9>::new();
+    v.dedup();
+
+    assert!(v.is_empty());
+}
+
+#[test]
+fn dedup_none_equal() {
+    let mut v = packed_ints![1, 2, 3, 4; U9];
+    v.dedup();
+
+    assert_eq!(v, packed_ints![1, 2, 3, 4; U9]);
+}
+
+#[test]
+fn drain() {
+    let mut v: PackedIntegers<U9> = (0..100).collect();
+    let drained: Vec<u32> = v.drain(10..70).collect();
+
+    assert_eq!(drained, (10..70).collect::<Vec<_>>());
+    assert_eq!(
+        v.iter().collect::<Vec<_>>(),
+        (0..10).chain(70..100).collect::<Vec<_>>()
+    );
+
+    assert_eq!(v.drain(..).count(), 40);
+    assert!(v.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "attempted to index slice up to maximum usize")]
+fn drain_inclusive_usize_max() {
+    let mut v = packed_ints![1, 2, 3; U9];
+    v.drain(..=usize::MAX);
+}
+
+#[test]
+#[should_panic]
+fn drain_out_of_range() {
+    let mut v = packed_ints![1, 2, 3; U9];
+    v.drain(2..4);
+}
+
+#[test]
+fn extend() {
+    let mut v = packed_ints![1, 2, 3; U9];
+    v.extend(vec![4, 5, 6]);
+    v.extend(7..=10);
+
+    assert_eq!(v, packed_ints![1, 2, 3, 4, 5, 6, 7, 8, 9, 10; U9]);
+}
+
+#[test]
+fn extend_after_truncate() {
+    let mut v = packed_ints![511, 511, 511, 511, 511; U9];
+    v.truncate(2);
+    v.extend(vec![0, 0]);
+
+    assert_eq!(v, packed_ints![511, 511, 0, 0; U9]);
+    assert_eq!(v.to_vec(), packed_ints![511, 511, 0, 0; U9].to_vec());
+}
+
+#[test]
+fn extend_from_packed() {
+    let mut v = packed_ints![1, 2, 3; U9];
+    let w = packed_ints![4, 5, 6, 7; U9];
+    v.extend_from_packed(&w);
+    v.extend_from_packed(&PackedIntegers::new());
+
+    assert_eq!(v, packed_ints![1, 2, 3, 4, 5, 6, 7; U9]);
+    assert_eq!(w, packed_ints![4, 5, 6, 7; U9]);
+}
+
+#[test]
+fn extend_from_slice() {
+    let values: Vec<u32> = (0..100).map(|i| i * 37 % 512).collect();
+    for split in 0..10 {
+        let mut v = PackedIntegers::<U9>::new();
+        for &value in &values[..split] {
+            v.push(value);
+        }
+        v.extend_from_slice(&values[split..]);
+
+        assert_eq!(v.iter().collect::<Vec<_>>(), values);
+    }
+}
+
+#[test]
+fn extend_from_slice_gt_max() {
+    let mut v = packed_ints![1; U9];
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        v.extend_from_slice(&[2, 3, 512, 4]);
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(v, packed_ints![1, 2, 3; U9]);
+}
+
+#[test]
+fn extend_saturating() {
+    let mut v = PackedIntegers::<U9>::new();
+    v.extend_saturating((0..10).map(|i| i * 100));
+
+    assert_eq!(
+        v,
+        packed_ints![0, 100, 200, 300, 400, 500, 511, 511, 511, 511; U9]
+    );
+}
+
+#[test]
+#[should_panic]
+fn fill_gt_max() {
+    let mut v = packed_ints![1, 2, 3; U9];
+    v.fill(512);
+}
+
+#[test]
+fn fill_range_empty() {
+    let mut v = packed_ints![1, 2, 3; U9];
+    v.fill_range(1..1, 511);
+
+    assert_eq!(v, packed_ints![1, 2, 3; U9]);
+}
+
+#[test]
+fn fill_range_many_cells() {
+    // 9-bit integers repeat their pattern every 9 cells, so this fill covers whole periods as
+    // well as partial cells at each end.
+    let mut v: PackedIntegers<U9> = (0..100).collect();
+    v.fill_range(3..90, 341);
+
+    for (i, value) in v.iter().enumerate() {
+        assert_eq!(value, if (3..90).contains(&i) { 341 } else { i as u32 });
+    }
+}
+
+#[test]
+fn fill_range_word_boundary() {
+    let mut v = packed_ints![1, 2, 3, 4, 5, 6, 7, 8; U8];
+    v.fill_range(2..6, 9);
+
+    assert_eq!(v.to_vec(), vec![0x09090201, 0x08070909]);
+}
+
 #[test]
 fn from_vec_u8_eq() {
     let b = vec![0b11111110_11111101_11111100_11111011];
@@ -144,6 +447,50 @@ fn get_no_span() {
     assert_eq!(v.get(5), None);
 }
 
+#[test]
+fn get_u32() {
+    let v = packed_ints![0, u32::MAX, 12345; U32];
+
+    assert_eq!(v.get(0), Some(0));
+    assert_eq!(v.get(1), Some(u32::MAX));
+    assert_eq!(v.get(2), Some(12345));
+    assert_eq!(v.get(3), None);
+}
+
+#[test]
+fn get_u33_span() {
+    let v = packed_ints![(1 << 33) - 1, 1 << 32, 1, 0x1_2345_6789; U33];
+
+    assert_eq!(v.get(0), Some((1 << 33) - 1));
+    assert_eq!(v.get(1), Some(1 << 32));
+    assert_eq!(v.get(2), Some(1));
+    assert_eq!(v.get(3), Some(0x1_2345_6789));
+    assert_eq!(v.get(4), None);
+}
+
+#[test]
+fn get_u63_span_three_cells() {
+    let mut v = PackedIntegers::<U63>::new();
+    for i in 0..10 {
+        v.push(U63::MAX - i);
+    }
+
+    // Elements at odd positions start partway through a buffer cell and span 3 cells.
+    for i in 0..10 {
+        assert_eq!(v.get(i as usize), Some(U63::MAX - i));
+    }
+}
+
+#[test]
+fn get_u64() {
+    let v = packed_ints![u64::MAX, 0, u64::MAX - 1; U64];
+
+    assert_eq!(v.get(0), Some(u64::MAX));
+    assert_eq!(v.get(1), Some(0));
+    assert_eq!(v.get(2), Some(u64::MAX - 1));
+    assert_eq!(v.to_vec().len(), 6);
+}
+
 #[test]
 fn insert() {
     let mut v = packed_ints![1, 2, 3; U8];
@@ -182,6 +529,97 @@ fn insert_gt_len() {
     v.insert(2, 4);
 }
 
+#[test]
+#[should_panic(expected = "insertion index (is 2) should be <= len (is 1)")]
+fn insert_gt_len_message() {
+    let mut v = packed_ints![1; U8];
+    v.insert(2, 4);
+}
+
+#[test]
+fn insert_has_span() {
+    // The integer at position 3 spans bits 27..36, across the first cell boundary.
+    let mut v = packed_ints![1, 2, 3, 4, 5; U9];
+    v.insert(3, 511);
+
+    assert_eq!(v, packed_ints![1, 2, 3, 511, 4, 5; U9]);
+    assert_eq!(v.to_vec(), packed_ints![1, 2, 3, 511, 4, 5; U9].to_vec());
+}
+
+#[test]
+fn insert_many() {
+    let mut v = packed_ints![1, 5; U9];
+    v.insert_many(1, &[2, 3, 4]);
+    v.insert_many(5, &[6]);
+    v.insert_many(0, &[]);
+
+    assert_eq!(v, packed_ints![1, 2, 3, 4, 5, 6; U9]);
+}
+
+#[test]
+#[should_panic(expected = "insertion index (is 3) should be <= len (is 2)")]
+fn insert_many_gt_len_message() {
+    let mut v = packed_ints![1, 5; U9];
+    v.insert_many(3, &[2]);
+}
+
+#[test]
+#[should_panic]
+fn insert_many_gt_max() {
+    let mut v = packed_ints![1, 5; U9];
+    v.insert_many(1, &[2, 512]);
+}
+
+#[test]
+fn insert_word_boundary() {
+    let mut v = packed_ints![1, 2, 3, 4, 5, 6, 7, 8; U8];
+    v.insert(4, 9);
+
+    assert_eq!(v.to_vec(), vec![0x04030201, 0x07060509, 0x00000008]);
+}
+
+#[test]
+fn into_iter_decode_into() {
+    let mut v: PackedIntegers<U9> = (0..20).collect();
+    let mut iter = v.drain(3..15);
+    let mut out = [0; 5];
+
+    assert_eq!(iter.next_chunk::<2>(), Some([3, 4]));
+    assert_eq!(iter.decode_into(&mut out), 5);
+    assert_eq!(out, [5, 6, 7, 8, 9]);
+    assert_eq!(iter.nth(1), Some(11));
+    assert_eq!(iter.next_chunk::<4>(), None);
+    assert_eq!(iter.decode_into(&mut out), 3);
+    assert_eq!(out[..3], [12, 13, 14]);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn into_iter_double_ended() {
+    let v: PackedIntegers<U9> = (0..10).collect();
+    let mut iter = v.into_iter();
+
+    assert_eq!(iter.len(), 10);
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.next_back(), Some(9));
+    assert_eq!(iter.nth(2), Some(3));
+    assert_eq!(iter.nth_back(1), Some(7));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.rev().collect::<Vec<_>>(), vec![6, 5, 4]);
+}
+
+#[test]
+fn into_iter_fused() {
+    let mut iter = packed_ints![1; U9].into_iter();
+
+    assert_eq!(iter.next(), Some(1));
+    for _ in 0..3 {
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+}
+
 #[test]
 fn into_iter_move() {
     let v = packed_ints![251, 252, 253, 254, 255; U8];
@@ -223,6 +661,14 @@ fn is_empty() {
     assert!(!v.is_empty());
 }
 
+#[test]
+fn is_sorted() {
+    assert!(PackedIntegers::<U9>::new().is_sorted());
+    assert!(packed_ints![5; U9].is_sorted());
+    assert!(packed_ints![1, 2, 2, 3; U9].is_sorted());
+    assert!(!packed_ints![1, 2, 3, 2; U9].is_sorted());
+}
+
 #[test]
 fn iter() {
     let v = packed_ints![507, 508, 509, 510, 511; U9];
@@ -239,6 +685,81 @@ fn iter() {
     // v.push(506);
 }
 
+#[test]
+fn iter_decode_into_mixed() {
+    let v: PackedIntegers<U9> = (0..100).collect();
+    let mut iter = v.iter();
+    let mut out = [0; 7];
+
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.decode_into(&mut out), 7);
+    assert_eq!(out, [1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(iter.nth(10), Some(18));
+    assert_eq!(iter.next_back(), Some(99));
+    assert_eq!(iter.next_chunk::<3>(), Some([19, 20, 21]));
+
+    let mut rest = [0; 100];
+    assert_eq!(iter.decode_into(&mut rest), 77);
+    assert_eq!(rest[..77], (22..99).collect::<Vec<_>>()[..]);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_chunk::<0>(), Some([]));
+}
+
+#[test]
+fn iter_double_ended() {
+    let v: PackedIntegers<U9> = (0..10).collect();
+
+    assert_eq!(
+        v.iter().rev().collect::<Vec<_>>(),
+        (0..10).rev().collect::<Vec<_>>()
+    );
+    assert_eq!(v.slice(2..8).iter().rev().nth(1), Some(6));
+
+    let mut iter = v.iter();
+    assert_eq!(iter.nth(3), Some(3));
+    assert_eq!(iter.nth_back(3), Some(6));
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.next_back(), Some(5));
+    assert_eq!(iter.next(), Some(4));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
+
+#[test]
+fn iter_nth_past_end() {
+    let v = packed_ints![1, 2, 3; U9];
+    let mut iter = v.iter();
+
+    assert_eq!(iter.nth(usize::MAX), None);
+    assert_eq!(iter.len(), 0);
+
+    let mut iter = v.iter();
+    assert_eq!(iter.nth_back(3), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn iter_size_hint() {
+    let v: PackedIntegers<U9> = (0..10).collect();
+    let mut iter = v.iter();
+    iter.next();
+    iter.next_back();
+
+    assert_eq!(iter.size_hint(), (8, Some(8)));
+    assert_eq!(iter.len(), 8);
+}
+
+#[test]
+fn iter_slice_has_span() {
+    // The slice starts 27 bits into the first cell, so its first integer spans two cells.
+    let v = packed_ints![1, 2, 3, 511, 4, 5, 6, 7; U9];
+    let slice = v.slice(3..);
+
+    assert_eq!(slice.iter().collect::<Vec<_>>(), vec![511, 4, 5, 6, 7]);
+    assert_eq!(slice.iter().nth(3), Some(6));
+    assert_eq!(slice.iter().next_chunk::<2>(), Some([511, 4]));
+}
+
 #[test]
 fn ord_eq() {
     let v1 = packed_ints![; U8];
@@ -288,6 +809,42 @@ fn ord_lt() {
     assert_eq!(v5.cmp(&v6), Ordering::Less);
 }
 
+#[test]
+fn packed_integers_error_display() {
+    let err = PackedIntegersError::ValueOutOfRange {
+        value: 600,
+        max: 511,
+    };
+    assert_eq!(
+        err.to_string(),
+        "value (is 600) is outside the range 0..=511"
+    );
+
+    let err = PackedIntegersError::IndexOutOfBounds { index: 3, len: 2 };
+    assert_eq!(
+        err.to_string(),
+        "index out of bounds: the len is 2 but the index is 3"
+    );
+
+    let err = PackedIntegersError::BufferTooSmall {
+        len: 1,
+        required: 2,
+    };
+    assert_eq!(
+        err.to_string(),
+        "vector is too small (len 1, should be at least 2)"
+    );
+}
+
+#[test]
+fn partition_point() {
+    let v: PackedIntegers<U9> = (0..100).collect();
+
+    assert_eq!(v.partition_point(|&x| x < 37), 37);
+    assert_eq!(v.partition_point(|_| true), 100);
+    assert_eq!(v.partition_point(|_| false), 0);
+}
+
 #[test]
 fn pop() {
     let mut v = packed_ints![100, 200, 300, 400, 500; U10];
@@ -330,6 +887,41 @@ fn push_gt_max() {
     v.push(1024);
 }
 
+#[test]
+#[should_panic(expected = "value (is 1024) is outside the range 0..=1023")]
+fn push_gt_max_message() {
+    let mut v = PackedIntegers::<U10>::new();
+    v.push(1024);
+}
+
+#[test]
+#[should_panic]
+fn push_gt_max_u40() {
+    let mut v = PackedIntegers::<U40>::new();
+    v.push(1 << 40);
+}
+
+#[test]
+fn push_saturating_u64() {
+    let mut v = PackedIntegers::<U64>::new();
+    v.push_saturating(u64::MAX);
+    let mut v33 = PackedIntegers::<U33>::new();
+    v33.push_saturating(u64::MAX);
+
+    assert_eq!(v.get(0), Some(u64::MAX));
+    assert_eq!(v33.get(0), Some(U33::MAX));
+}
+
+#[test]
+fn push_wrapping() {
+    let mut v = PackedIntegers::<U3>::new();
+    for i in 0..10 {
+        v.push_wrapping(i);
+    }
+
+    assert_eq!(v, packed_ints![0, 1, 2, 3, 4, 5, 6, 7, 0, 1; U3]);
+}
+
 #[test]
 fn remove() {
     let mut v = packed_ints![251, 252, 253, 254, 255; U8];
@@ -350,6 +942,165 @@ fn remove_eq_len() {
     v.remove(2);
 }
 
+#[test]
+#[should_panic(expected = "removal index (is 2) should be < len (is 2)")]
+fn remove_eq_len_message() {
+    let mut v = packed_ints![251, 252; U8];
+    v.remove(2);
+}
+
+#[test]
+fn remove_has_span() {
+    let mut v = packed_ints![1, 2, 3, 511, 4, 5; U9];
+
+    assert_eq!(v.remove(3), 511);
+    assert_eq!(v, packed_ints![1, 2, 3, 4, 5; U9]);
+    assert_eq!(v.to_vec(), packed_ints![1, 2, 3, 4, 5; U9].to_vec());
+}
+
+#[test]
+fn remove_word_boundary() {
+    let mut v = packed_ints![1, 2, 3, 4, 5, 6, 7, 8, 9; U8];
+
+    assert_eq!(v.remove(3), 4);
+    assert_eq!(v.to_vec(), vec![0x05030201, 0x09080706]);
+}
+
+#[test]
+fn repack_narrow() {
+    let v = packed_ints![1, 2, 3, 127; U12];
+
+    assert_eq!(v.repack::<U7>().unwrap(), packed_ints![1, 2, 3, 127; U7]);
+}
+
+#[test]
+fn repack_narrow_out_of_range() {
+    let v = packed_ints![1, 200, 3, 400; U12];
+    let err = v.repack::<U7>().unwrap_err();
+
+    assert_eq!(
+        err,
+        RepackError {
+            index: 1,
+            value: 200,
+            max: 127
+        }
+    );
+}
+
+#[test]
+fn repack_same_width() {
+    let mut v = packed_ints![507, 508, 509, 510, 511; U9];
+    v.pop();
+
+    assert_eq!(
+        v.repack::<U9>().unwrap(),
+        packed_ints![507, 508, 509, 510; U9]
+    );
+}
+
+#[test]
+fn repack_wide_to_narrow_value() {
+    let v = packed_ints![1 << 20, 3; U40];
+
+    assert_eq!(v.repack::<U21>().unwrap(), packed_ints![1 << 20, 3; U21]);
+}
+
+#[test]
+fn repack_widen() {
+    let v = packed_ints![4095, 0, 1, 2048, 4094; U12];
+
+    assert_eq!(
+        v.repack::<U17>().unwrap(),
+        packed_ints![4095, 0, 1, 2048, 4094; U17]
+    );
+    assert_eq!(
+        v.repack::<U40>().unwrap(),
+        packed_ints![4095, 0, 1, 2048, 4094; U40]
+    );
+}
+
+#[test]
+fn resize() {
+    let mut v = packed_ints![511, 511, 511, 511, 511; U9];
+    v.truncate(1);
+    v.resize(40, 3);
+
+    assert_eq!(v.len(), 40);
+    assert_eq!(v.get(0), Some(511));
+    assert!(v.iter().skip(1).all(|x| x == 3));
+
+    v.resize(2, 0);
+    assert_eq!(v, packed_ints![511, 3; U9]);
+}
+
+#[test]
+fn resize_with() {
+    let mut v = packed_ints![1; U9];
+    let mut i = 1;
+    v.resize_with(5, || {
+        i *= 2;
+        i
+    });
+
+    assert_eq!(v, packed_ints![1, 2, 4, 8, 16; U9]);
+
+    v.resize_with(1, || unreachable!());
+    assert_eq!(v, packed_ints![1; U9]);
+}
+
+#[test]
+fn retain_all_removed() {
+    let mut v = packed_ints![1, 2, 3, 4; U9];
+    v.retain(|_| false);
+
+    assert!(v.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn retain_mut_gt_max() {
+    let mut v = packed_ints![100, 200, 300; U9];
+    v.retain_mut(|v| {
+        *v += 300;
+        true
+    });
+}
+
+#[test]
+fn retain_mut_panic() {
+    let mut v = packed_ints![1, 2, 3, 4, 5, 6; U9];
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        v.retain_mut(|v| {
+            if *v == 4 {
+                panic!("closure panicked");
+            }
+            *v % 2 == 1
+        })
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(v, packed_ints![1, 3, 4, 5, 6; U9]);
+}
+
+#[test]
+fn retain_none_removed() {
+    let mut v = packed_ints![1, 2, 3, 4; U9];
+    v.retain(|_| true);
+
+    assert_eq!(v, packed_ints![1, 2, 3, 4; U9]);
+}
+
+#[test]
+fn retain_word_boundary() {
+    // Removing the first integer moves each of the others back across a cell boundary.
+    let mut v = packed_ints![0, 1, 2, 3, 4, 5, 6, 7; U9];
+    v.retain(|&x| x != 0 && x != 5);
+
+    assert_eq!(v, packed_ints![1, 2, 3, 4, 6, 7; U9]);
+    assert_eq!(v.to_vec(), packed_ints![1, 2, 3, 4, 6, 7; U9].to_vec());
+}
+
 #[test]
 fn set() {
     let mut v = packed_ints![251, 252, 253, 254, 255; U8];
@@ -360,6 +1111,21 @@ fn set() {
     assert_eq!(v, packed_ints![100, 252, 150, 254, 200; U8]);
 }
 
+#[test]
+#[should_panic(expected = "value (is 512) is outside the range 0..=511")]
+fn set_gt_max_message() {
+    let mut v = packed_ints![1, 2; U9];
+    v.set(1, 512);
+}
+
+#[test]
+fn set_has_span() {
+    let mut v = packed_ints![1, 2, 3, 4, 5; U9];
+    v.set(3, 511);
+
+    assert_eq!(v, packed_ints![1, 2, 3, 511, 5; U9]);
+}
+
 #[test]
 #[should_panic]
 fn set_oob() {
@@ -367,6 +1133,181 @@ fn set_oob() {
     v.set(1, 200);
 }
 
+#[test]
+fn set_saturating_has_span() {
+    let mut v = packed_ints![1, 2, 3, 4, 5; U9];
+    v.set_saturating(3, u32::MAX);
+
+    assert_eq!(v, packed_ints![1, 2, 3, 511, 5; U9]);
+}
+
+#[test]
+fn set_u47_span() {
+    let mut v = packed_ints![1, 2, 3, 4, 5; U47];
+    v.set(1, U47::MAX);
+    v.set(3, 0x5555_5555_5555);
+
+    assert_eq!(v, packed_ints![1, U47::MAX, 3, 0x5555_5555_5555, 5; U47]);
+}
+
+#[test]
+fn set_wrapping_has_span() {
+    let mut v = packed_ints![1, 2, 3, 4, 5; U9];
+    v.set_wrapping(3, 0xffff_fe00 | 7);
+
+    assert_eq!(v, packed_ints![1, 2, 3, 7, 5; U9]);
+}
+
+#[test]
+#[should_panic]
+fn set_wrapping_oob() {
+    let mut v = packed_ints![1, 2, 3; U9];
+    v.set_wrapping(3, 0);
+}
+
+#[test]
+fn shrink_to_fit() {
+    let mut v: PackedIntegers<U9> = (0..100).collect();
+    v.truncate(10);
+    v.shrink_to_fit();
+
+    assert_eq!(v.capacity(), 10);
+    assert_eq!(v.iter().collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+
+    v.push(10);
+    assert_eq!(v.get(10), Some(10));
+}
+
+#[test]
+fn sort_by_key_panic() {
+    // Panic once while insertion sorting the first run, and once while merging from the scratch
+    // buffer back into the vector.
+    for &panic_at in &[500, 1700] {
+        let mut v: PackedIntegers<U9> = (0..100).rev().collect();
+        let mut calls = 0;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            v.sort_by_key(|&x| {
+                calls += 1;
+                if calls == panic_at {
+                    panic!("key panicked");
+                }
+                x
+            })
+        }));
+
+        assert!(result.is_err());
+        let mut values: Vec<u32> = v.iter().collect();
+        values.sort_unstable();
+        assert_eq!(values, (0..100).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn sort_by_key_stable() {
+    // More than 32 integers, so runs are merged as well as insertion sorted.
+    let mut v: PackedIntegers<U9> = (0..100).collect();
+    v.sort_by_key(|&x| x % 4 == 0);
+
+    assert_eq!(
+        v.iter().collect::<Vec<_>>(),
+        (0..100)
+            .filter(|x| x % 4 != 0)
+            .chain((0..100).step_by(4))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn sort_few_distinct() {
+    let mut v: PackedIntegers<U20> = (0..5000).map(|i| (i % 3) << 18).collect();
+    v.sort();
+
+    assert!(v.is_sorted());
+    assert_eq!(v.iter().filter(|&x| x == 1 << 18).count(), 1667);
+}
+
+#[test]
+fn sort_unstable_multiple_passes() {
+    // 17-bit integers are distributed on three digits, and there are enough of them that each
+    // pass is a radix pass rather than an insertion sort.
+    let mut v: PackedIntegers<U17> = (0..100).rev().map(|i| i * 1300).collect();
+    v.sort_unstable();
+
+    assert_eq!(v, (0..100).map(|i| i * 1300).collect());
+}
+
+#[test]
+fn sort_unstable_u1() {
+    let mut v = packed_ints![1, 0, 1, 1, 0; U1];
+    v.sort_unstable();
+
+    assert_eq!(v, packed_ints![0, 0, 1, 1, 1; U1]);
+}
+
+#[test]
+fn splice() {
+    let mut v: PackedIntegers<U9> = (0..50).collect();
+    let removed: Vec<u32> = v.splice(10..40, 500..505).collect();
+
+    assert_eq!(removed, (10..40).collect::<Vec<_>>());
+    assert_eq!(
+        v.iter().collect::<Vec<_>>(),
+        (0..10).chain(500..505).chain(40..50).collect::<Vec<_>>()
+    );
+
+    v.splice(..0, vec![1, 2]);
+    v.splice(v.len().., vec![3]);
+
+    assert_eq!(v.len(), 28);
+    assert_eq!(v.get(0), Some(1));
+    assert_eq!(v.get(27), Some(3));
+}
+
+#[test]
+#[should_panic]
+fn split_off_gt_len() {
+    let mut v = packed_ints![1, 2, 3; U9];
+    v.split_off(4);
+}
+
+#[test]
+fn split_off_unaligned() {
+    // The split falls 27 bits into the first cell, so every cell of the tail is reassembled.
+    let mut v = packed_ints![1, 2, 3, 511, 4, 5, 6, 7; U9];
+    let tail = v.split_off(3);
+
+    assert_eq!(v, packed_ints![1, 2, 3; U9]);
+    assert_eq!(tail, packed_ints![511, 4, 5, 6, 7; U9]);
+    assert_eq!(tail.to_vec(), packed_ints![511, 4, 5, 6, 7; U9].to_vec());
+}
+
+#[test]
+fn split_off_word_aligned() {
+    let mut v = packed_ints![1, 2, 3, 4, 5, 6, 7; U8];
+    let tail = v.split_off(4);
+
+    assert_eq!(v.to_vec(), vec![0x04030201]);
+    assert_eq!(tail.to_vec(), vec![0x00070605]);
+    assert!(v.split_off(4).is_empty());
+}
+
+#[test]
+fn swap_remove() {
+    let mut v = packed_ints![100, 200, 300; U9];
+
+    assert_eq!(v.swap_remove(0), 100);
+    assert_eq!(v, packed_ints![300, 200; U9]);
+    assert_eq!(v.swap_remove(1), 200);
+    assert_eq!(v, packed_ints![300; U9]);
+}
+
+#[test]
+#[should_panic]
+fn swap_remove_eq_len() {
+    let mut v = packed_ints![100, 200, 300; U9];
+    v.swap_remove(3);
+}
+
 #[test]
 fn to_vec_clear() {
     let mut v = packed_ints![
@@ -503,3 +1444,272 @@ fn truncate_gt() {
     assert_eq!(v.len(), 5);
     assert_eq!(v, packed_ints![251, 252, 253, 254, 255; U8]);
 }
+
+#[test]
+fn try_from_vec_u9_gt() {
+    let b = vec![0b10011_111111101_111111100_111111011];
+
+    assert_eq!(
+        PackedIntegers::<U9>::try_from_vec(b, 4),
+        Err(PackedIntegersError::BufferTooSmall {
+            len: 1,
+            required: 2
+        })
+    );
+}
+
+#[test]
+fn try_insert_gt_len() {
+    let mut v = packed_ints![251, 252; U8];
+
+    assert_eq!(
+        v.try_insert(3, 253),
+        Err(PackedIntegersError::IndexOutOfBounds { index: 3, len: 2 })
+    );
+    assert_eq!(v, packed_ints![251, 252; U8]);
+}
+
+#[test]
+fn try_insert_gt_max() {
+    let mut v = packed_ints![251, 252; U8];
+
+    assert_eq!(
+        v.try_insert(0, 256),
+        Err(PackedIntegersError::ValueOutOfRange {
+            value: 256,
+            max: 255
+        })
+    );
+    assert_eq!(v, packed_ints![251, 252; U8]);
+}
+
+#[test]
+fn try_push_gt_max_u40() {
+    let mut v = PackedIntegers::<U40>::new();
+
+    assert_eq!(
+        v.try_push(1 << 40),
+        Err(PackedIntegersError::ValueOutOfRange {
+            value: 1 << 40,
+            max: (1 << 40) - 1
+        })
+    );
+    assert!(v.is_empty());
+    assert_eq!(v.try_push((1 << 40) - 1), Ok(()));
+}
+
+#[test]
+fn try_remove_eq_len() {
+    let mut v = packed_ints![251, 252; U8];
+
+    assert_eq!(
+        v.try_remove(2),
+        Err(PackedIntegersError::IndexOutOfBounds { index: 2, len: 2 })
+    );
+    assert_eq!(v.try_remove(0), Ok(251));
+}
+
+#[test]
+fn try_set_oob() {
+    let mut v = packed_ints![251, 252; U8];
+
+    assert_eq!(
+        v.try_set(2, 0),
+        Err(PackedIntegersError::IndexOutOfBounds { index: 2, len: 2 })
+    );
+    assert_eq!(
+        v.try_set(0, 256),
+        Err(PackedIntegersError::ValueOutOfRange {
+            value: 256,
+            max: 255
+        })
+    );
+    assert_eq!(v, packed_ints![251, 252; U8]);
+}
+
+#[test]
+fn word_u16_from_vec() {
+    let v = PackedIntegers::<U5, u16>::from_vec(vec![0b0_00011_00010_00001, 0b1111], 4);
+
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![1, 2, 3, 0b11110]);
+}
+
+#[test]
+#[should_panic]
+fn word_u16_from_vec_gt() {
+    let _v = PackedIntegers::<U5, u16>::from_vec(vec![0], 4);
+}
+
+#[test]
+fn word_u16_sort_unstable() {
+    let mut v: PackedIntegers<U64, u16> = (0..40u64).rev().map(|i| i << 58 | i).collect();
+    v.sort_unstable();
+
+    assert_eq!(v, (0..40u64).map(|i| i << 58 | i).collect());
+}
+
+#[test]
+fn word_u64() {
+    let values: Vec<u64> = (0..50).map(|i| (1 << 40) + i * 12345).collect();
+    let mut v = PackedIntegers::<U41, u64>::new();
+    for &value in &values {
+        v.push(value);
+    }
+
+    assert_eq!(v.to_vec().len(), 33);
+    assert_eq!(v.iter().collect::<Vec<_>>(), values);
+    assert_eq!(v.remove(3), values[3]);
+    assert_eq!(v.pop(), values.last().copied());
+}
+
+#[test]
+fn word_u64_extend_from_slice() {
+    let values = [u64::MAX >> 1, 1, u64::MAX >> 1, 2, 3];
+    let mut v = PackedIntegers::<U63, u64>::new();
+    v.push(7);
+    v.extend_from_slice(&values);
+
+    assert_eq!(v.len(), 6);
+    assert_eq!(v.iter().skip(1).collect::<Vec<_>>(), values);
+}
+
+#[test]
+fn word_u64_fill_range() {
+    let mut v = PackedIntegers::<U33, u64>::new();
+    v.extend_from_slice(&[1, 2, 3, 4, 5]);
+    v.fill_range(1..4, U33::MAX);
+
+    assert_eq!(
+        v.iter().collect::<Vec<_>>(),
+        vec![1, U33::MAX, U33::MAX, U33::MAX, 5]
+    );
+}
+
+#[test]
+fn word_u64_insert_remove() {
+    let mut v = PackedIntegers::<U47, u64>::new();
+    v.extend_from_slice(&[1, 2, 3]);
+    v.insert(1, U47::MAX);
+
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![1, U47::MAX, 2, 3]);
+    assert_eq!(v.remove(0), 1);
+    assert_eq!(v.to_vec(), vec![U47::MAX | 2 << 47, 3 << 30, 0]);
+}
+
+#[test]
+fn word_u64_iter_u63() {
+    // After the first integer, each one spans two cells, so the bit buffer holds more than 64 bits.
+    let values = [U63::MAX, 1, U63::MAX, 2, 3];
+    let mut v = PackedIntegers::<U63, u64>::new();
+    v.extend_from_slice(&values);
+
+    assert_eq!(v.iter().collect::<Vec<_>>(), values);
+    assert_eq!(v.iter().nth(2), Some(U63::MAX));
+    assert_eq!(v.into_iter().collect::<Vec<_>>(), values);
+}
+
+#[test]
+fn word_u64_repack() {
+    let mut v = PackedIntegers::<U9, u64>::new();
+    v.push(300);
+    v.push(511);
+
+    let w = v.repack::<U10>().unwrap();
+    assert_eq!(w.to_vec(), vec![511 << 10 | 300]);
+}
+
+#[test]
+fn word_u64_split_off() {
+    let mut v = PackedIntegers::<U33, u64>::new();
+    v.extend_from_slice(&[1, 2, 3, U33::MAX]);
+    let tail = v.split_off(1);
+
+    assert_eq!(v.to_vec(), vec![1]);
+    assert_eq!(tail.iter().collect::<Vec<_>>(), vec![2, 3, U33::MAX]);
+    assert_eq!(tail.to_vec(), vec![3 << 33 | 2, U33::MAX << 2]);
+}
+
+#[test]
+fn word_u8() {
+    let mut v = PackedIntegers::<U12, u8>::new();
+    v.push(0xabc);
+    v.push(0x123);
+    v.push(0xfff);
+
+    assert_eq!(v.to_vec(), vec![0xbc, 0x3a, 0x12, 0xff, 0x0f]);
+    assert_eq!(v.get(1), Some(0x123));
+
+    v.set(1, 0x456);
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![0xabc, 0x456, 0xfff]);
+}
+
+#[test]
+fn word_u8_append() {
+    // `v` ends 2 bits into its second cell, and each appended integer spans two cells.
+    let mut v = PackedIntegers::<U5, u8>::new();
+    v.extend_from_slice(&[31, 1]);
+    let mut w = PackedIntegers::<U5, u8>::new();
+    w.extend_from_slice(&[2, 3, 31]);
+    v.append(&mut w);
+
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![31, 1, 2, 3, 31]);
+    assert_eq!(
+        v.to_vec(),
+        vec![0b001_11111, 0b1_00010_00, 0b1111_0001, 0b1]
+    );
+}
+
+#[test]
+fn word_u8_capacity() {
+    let v = PackedIntegers::<U8, u8>::with_capacity(3);
+
+    assert_eq!(v.capacity(), 3);
+}
+
+#[test]
+fn word_u8_collect() {
+    let v: PackedIntegers<U3, u8> = (0..8).collect();
+
+    assert_eq!(v.to_vec(), vec![0b10_001_000, 0b1_100_011_0, 0b111_110_10]);
+}
+
+#[test]
+fn word_u8_fill_range() {
+    let mut v: PackedIntegers<U3, u8> = (0..8).collect();
+    v.fill_range(1..7, 5);
+
+    assert_eq!(v.to_vec(), vec![0b01_101_000, 0b1_101_101_1, 0b111_101_10]);
+}
+
+#[test]
+fn word_u8_iter_u1() {
+    let v: PackedIntegers<U1, u8> = (0..20).map(|i| (i % 3 == 0) as u32).collect();
+
+    assert_eq!(v.to_vec(), vec![0b01001001, 0b10010010, 0b0100]);
+    assert_eq!(
+        v.iter().collect::<Vec<_>>(),
+        (0..20).map(|i| (i % 3 == 0) as u32).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn word_u8_iter_u64() {
+    // Each integer spans eight cells.
+    let values = [u64::MAX, 0x0123_4567_89ab_cdef, 1];
+    let mut v = PackedIntegers::<U64, u8>::new();
+    v.extend_from_slice(&values);
+
+    assert_eq!(v.iter().collect::<Vec<_>>(), values);
+    assert_eq!(v.iter().nth(1), Some(0x0123_4567_89ab_cdef));
+}
+
+#[test]
+fn word_u8_u64_values() {
+    let mut v = PackedIntegers::<U64, u8>::new();
+    v.push(u64::MAX);
+    v.push(0x0123_4567_89ab_cdef);
+
+    assert_eq!(v.to_vec().len(), 16);
+    assert_eq!(v.get(0), Some(u64::MAX));
+    assert_eq!(v.get(1), Some(0x0123_4567_89ab_cdef));
+}
@@ -0,0 +1,82 @@
+use packed_integers::*;
+
+#[test]
+fn as_packed_ref() {
+    let mut buf = packed_ints![1, 2, 3; U9].to_vec();
+    let v = PackedIntegersMut::<U9>::from_slice(&mut buf, 3);
+
+    assert_eq!(v.as_packed_ref().to_packed(), packed_ints![1, 2, 3; U9]);
+}
+
+#[test]
+fn fill() {
+    let mut buf = [u32::MAX; 4];
+    let mut v = PackedIntegersMut::<U9>::from_slice(&mut buf, 10);
+    v.fill(0b101010101);
+
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![0b101010101; 10]);
+    // Bits past the end of the view are untouched.
+    assert_eq!(buf[2] >> 26, 0b111111);
+    assert_eq!(buf[3], u32::MAX);
+}
+
+#[test]
+#[should_panic]
+fn fill_gt_max() {
+    let mut buf = [0];
+    let mut v = PackedIntegersMut::<U4>::from_slice(&mut buf, 8);
+    v.fill(16);
+}
+
+#[test]
+#[should_panic]
+fn from_slice_gt() {
+    let mut buf = [0];
+    let _v = PackedIntegersMut::<U9>::from_slice(&mut buf, 4);
+}
+
+#[test]
+fn set_in_larger_buffer() {
+    let mut arena = [0u64; 8];
+    {
+        let mut v = PackedIntegersMut::<U40, u64>::from_slice(&mut arena[2..5], 4);
+        v.set(0, 1 << 39);
+        v.set(1, 12345);
+        v.set(3, (1 << 40) - 1);
+    }
+
+    assert_eq!(arena[0..2], [0, 0]);
+    assert_eq!(arena[5..], [0, 0, 0]);
+
+    let v = PackedIntegersRef::<U40, u64>::from_slice(&arena[2..5], 4);
+    assert_eq!(
+        v.iter().collect::<Vec<_>>(),
+        vec![1 << 39, 12345, 0, (1 << 40) - 1]
+    );
+}
+
+#[test]
+#[should_panic]
+fn set_oob() {
+    let mut buf = [0];
+    let mut v = PackedIntegersMut::<U8>::from_slice(&mut buf, 2);
+    v.set(2, 1);
+}
+
+#[test]
+fn swap() {
+    let mut buf = packed_ints![507, 508, 509, 510, 511; U9].to_vec();
+    let mut v = PackedIntegersMut::<U9>::from_slice(&mut buf, 5);
+    v.swap(0, 3);
+    v.swap(4, 4);
+
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![510, 508, 509, 507, 511]);
+}
+
+#[test]
+#[should_panic]
+fn swap_oob() {
+    let mut buf = [0];
+    let mut v = PackedIntegersMut::<U8>::from_slice(&mut buf, 2);
+    v.swap(0, 2);
+}
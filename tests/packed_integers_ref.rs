@@ -0,0 +1,86 @@
+#![allow(clippy::unusual_byte_groupings)]
+
+use packed_integers::*;
+use std::cmp::Ordering;
+
+#[test]
+fn debug() {
+    let buf = packed_ints![1, 2, 3; U9].to_vec();
+    let v = PackedIntegersRef::<U9>::from_slice(&buf, 3);
+
+    assert_eq!(format!("{:?}", v), "(U9) [1, 2, 3]");
+}
+
+#[test]
+fn from_slice_u9_eq() {
+    let b = [
+        0b10011_111111101_111111100_111111011,
+        0b111111111_111111110_1100,
+    ];
+    let v = PackedIntegersRef::<U9>::from_slice(&b, 6);
+
+    assert_eq!(v.get(0), Some(0b111111011));
+    assert_eq!(v.get(1), Some(0b111111100));
+    assert_eq!(v.get(2), Some(0b111111101));
+    assert_eq!(v.get(3), Some(0b110010011));
+    assert_eq!(v.get(4), Some(0b111111110));
+    assert_eq!(v.get(5), Some(0b111111111));
+    assert_eq!(v.get(6), None);
+}
+
+#[test]
+#[should_panic]
+fn from_slice_u9_gt() {
+    let b = [
+        0b10011_111111101_111111100_111111011,
+        0b111111111_111111110_1100,
+    ];
+    let _v = PackedIntegersRef::<U9>::from_slice(&b, 8);
+}
+
+#[test]
+fn from_slice_word_u8() {
+    let b = [0xbc, 0x3a, 0x12];
+    let v = PackedIntegersRef::<U12, u8>::from_slice(&b, 2);
+
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![0xabc, 0x123]);
+}
+
+#[test]
+fn is_empty() {
+    let v = PackedIntegersRef::<U9>::from_slice(&[], 0);
+
+    assert!(v.is_empty());
+    assert_eq!(v.iter().next(), None);
+}
+
+#[test]
+fn iter_outlives_view() {
+    let buf = packed_ints![1, 2, 3; U40].to_vec();
+    let iter = {
+        let v = PackedIntegersRef::<U40>::from_slice(&buf, 3);
+        v.iter()
+    };
+
+    assert_eq!(iter.collect::<Vec<_>>(), vec![1, 2, 3]);
+}
+
+#[test]
+fn ord() {
+    let buf1 = packed_ints![1, 2, 3; U9].to_vec();
+    let buf2 = packed_ints![1, 4; U9].to_vec();
+    let v1 = PackedIntegersRef::<U9>::from_slice(&buf1, 3);
+    let v2 = PackedIntegersRef::<U9>::from_slice(&buf2, 2);
+
+    assert_eq!(v1.cmp(&v2), Ordering::Less);
+    assert_eq!(v1.cmp(&v1), Ordering::Equal);
+    assert_ne!(v1, v2);
+}
+
+#[test]
+fn to_packed() {
+    let buf = packed_ints![507, 508, 509, 510, 511; U9].to_vec();
+    let v = PackedIntegersRef::<U9>::from_slice(&buf, 4);
+
+    assert_eq!(v.to_packed(), packed_ints![507, 508, 509, 510; U9]);
+}
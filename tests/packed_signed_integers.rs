@@ -0,0 +1,112 @@
+use packed_integers::*;
+
+fn signed<T: PackedInt>(values: &[<T::Value as PackedValue>::Signed]) -> PackedSignedIntegers<T> {
+    let mut v = PackedSignedIntegers::new();
+    for &value in values {
+        v.push(value);
+    }
+    v
+}
+
+#[test]
+fn debug() {
+    let v = signed::<U9>(&[-1, 2]);
+
+    assert_eq!(format!("{:?}", v), "(I9) [-1, 2]");
+}
+
+#[test]
+fn encoding_is_zigzag() {
+    let v = signed::<U8>(&[0, -1, 1, -2]);
+
+    assert_eq!(v.to_vec(), vec![0b00000011_00000010_00000001_00000000]);
+}
+
+#[test]
+fn get_u1() {
+    let v = signed::<U1>(&[-1, 0, -1]);
+
+    assert_eq!(v.get(0), Some(-1));
+    assert_eq!(v.get(1), Some(0));
+    assert_eq!(v.get(2), Some(-1));
+    assert_eq!(v.get(3), None);
+}
+
+#[test]
+fn get_u32_limits() {
+    let v = signed::<U32>(&[i32::MIN, i32::MAX, 0]);
+
+    assert_eq!(v.iter().collect::<Vec<_>>(), vec![i32::MIN, i32::MAX, 0]);
+}
+
+#[test]
+fn get_u40_span() {
+    let min = -(1 << 39);
+    let max = (1 << 39) - 1;
+    let v = signed::<U40>(&[min, max, -12345, 12345]);
+
+    assert_eq!(v.get(0), Some(min));
+    assert_eq!(v.get(1), Some(max));
+    assert_eq!(v.get(2), Some(-12345));
+    assert_eq!(v.get(3), Some(12345));
+}
+
+#[test]
+fn get_u64_limits() {
+    let v = signed::<U64>(&[i64::MIN, i64::MAX, -1]);
+
+    assert_eq!(
+        v.into_iter().collect::<Vec<_>>(),
+        vec![i64::MIN, i64::MAX, -1]
+    );
+}
+
+#[test]
+fn insert_remove() {
+    let mut v = signed::<U9>(&[-1, -2, -3]);
+    v.insert(1, 200);
+
+    assert_eq!(v, signed::<U9>(&[-1, 200, -2, -3]));
+    assert_eq!(v.remove(3), -3);
+    assert_eq!(v, signed::<U9>(&[-1, 200, -2]));
+}
+
+#[test]
+fn ord() {
+    let v1 = signed::<U9>(&[-1, 2]);
+    let v2 = signed::<U9>(&[1, 2]);
+
+    assert!(v1 < v2);
+}
+
+#[test]
+fn pop() {
+    let mut v = signed::<U10>(&[-100, 200, -300]);
+
+    assert_eq!(v.pop(), Some(-300));
+    assert_eq!(v.pop(), Some(200));
+    assert_eq!(v.pop(), Some(-100));
+    assert_eq!(v.pop(), None);
+}
+
+#[test]
+#[should_panic]
+fn push_gt_max() {
+    let mut v = PackedSignedIntegers::<U10>::new();
+    v.push(512);
+}
+
+#[test]
+#[should_panic]
+fn push_lt_min() {
+    let mut v = PackedSignedIntegers::<U10>::new();
+    v.push(-513);
+}
+
+#[test]
+fn set() {
+    let mut v = signed::<U9>(&[1, 2, 3, 4, 5]);
+    v.set(3, -256);
+
+    assert_eq!(v, signed::<U9>(&[1, 2, 3, -256, 5]));
+}
@@ -0,0 +1,129 @@
+use packed_integers::*;
+use std::cmp::Ordering;
+
+#[test]
+fn debug() {
+    let v = packed_ints![1, 2, 3, 4; U9];
+
+    assert_eq!(format!("{:?}", v.slice(1..3)), "(U9) [2, 3]");
+}
+
+#[test]
+fn first_last() {
+    let v = packed_ints![100, 200, 300, 400; U9];
+    let s = v.slice(1..3);
+
+    assert_eq!(s.first(), Some(200));
+    assert_eq!(s.last(), Some(300));
+    assert_eq!(v.slice(2..2).first(), None);
+    assert_eq!(v.slice(2..2).last(), None);
+}
+
+#[test]
+fn is_empty() {
+    let v = packed_ints![100, 200; U9];
+
+    assert!(v.slice(..0).is_empty());
+    assert!(v.slice(2..).is_empty());
+    assert!(!v.slice(1..).is_empty());
+}
+
+#[test]
+fn iter_outlives_slice() {
+    let v = packed_ints![100, 200, 300; U9];
+    let iter = {
+        let s = v.slice(1..);
+        s.iter()
+    };
+
+    assert_eq!(iter.collect::<Vec<_>>(), vec![200, 300]);
+}
+
+#[test]
+fn ord() {
+    let v = packed_ints![1, 2, 3, 1, 2, 4; U9];
+
+    assert_eq!(v.slice(..3), v.slice(..3));
+    assert_ne!(v.slice(..3), v.slice(3..));
+    assert_eq!(v.slice(..3).cmp(&v.slice(3..)), Ordering::Less);
+    assert_eq!(v.slice(..2), v.slice(3..5));
+}
+
+#[test]
+#[should_panic(expected = "attempted to index slice from after maximum usize")]
+fn slice_excluded_usize_max() {
+    use std::ops::Bound;
+
+    let v = packed_ints![1, 2, 3; U9];
+    let _s = v.slice((Bound::Excluded(usize::MAX), Bound::Unbounded));
+}
+
+#[test]
+fn slice_nested() {
+    let mut v = PackedIntegers::<U9>::new();
+    for i in 0..100 {
+        v.push(i);
+    }
+    let s = v.slice(7..93).slice(11..).slice(..50).slice(3..=40);
+
+    assert_eq!(s.len(), 38);
+    assert_eq!(s.iter().collect::<Vec<_>>(), (21..59).collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic]
+fn slice_out_of_range() {
+    let v = packed_ints![100, 200, 300; U9];
+    let _s = v.slice(1..).slice(..3);
+}
+
+#[test]
+#[should_panic]
+fn slice_start_after_end() {
+    let v = packed_ints![100, 200, 300; U9];
+    #[allow(clippy::reversed_empty_ranges)]
+    let _s = v.slice(2..1);
+}
+
+#[test]
+fn split_at() {
+    let v = packed_ints![100, 200, 300, 400, 500; U9];
+    let (lo, hi) = v.slice(1..).split_at(2);
+
+    assert_eq!(lo.to_packed(), packed_ints![200, 300; U9]);
+    assert_eq!(hi.to_packed(), packed_ints![400, 500; U9]);
+
+    let (lo, hi) = v.slice(..).split_at(5);
+
+    assert_eq!(lo.len(), 5);
+    assert!(hi.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn split_at_gt() {
+    let v = packed_ints![100, 200, 300; U9];
+    let _s = v.slice(..).split_at(4);
+}
+
+#[test]
+fn to_packed() {
+    let v = packed_ints![507, 508, 509, 510, 511; U9];
+
+    assert_eq!(v.slice(3..).to_packed(), packed_ints![510, 511; U9]);
+    assert_eq!(v.slice(..).to_packed(), v);
+    assert_eq!(v.slice(5..).to_packed(), PackedIntegers::<U9>::new());
+}
+
+#[test]
+fn word_u8() {
+    let mut v = PackedIntegers::<U5, u8>::new();
+    for i in 0..20 {
+        v.push(i);
+    }
+    let s = v.slice(3..17).slice(2..);
+
+    assert_eq!(s.iter().collect::<Vec<_>>(), (5..17).collect::<Vec<_>>());
+    assert_eq!(s.get(4), Some(9));
+    assert_eq!(s.to_packed().len(), 12);
+}
@@ -0,0 +1,178 @@
+use packed_integers::*;
+
+fn zeros<T: PackedInt, W: Word>(len: usize) -> PackedIntegers<T, W> {
+    PackedIntegers::from_vec(vec![W::default(); len * T::NUM_BITS / W::NUM_BITS + 1], len)
+}
+
+#[test]
+fn debug() {
+    let mut v = packed_ints![1, 2, 3, 4; U9];
+
+    assert_eq!(format!("{:?}", v.slice_mut(1..3)), "(U9) [2, 3]");
+}
+
+#[test]
+fn first_last() {
+    let mut v = packed_ints![100, 200, 300, 400; U9];
+    let s = v.slice_mut(1..3);
+
+    assert_eq!(s.first(), Some(200));
+    assert_eq!(s.last(), Some(300));
+    assert_eq!(v.slice_mut(2..2).first(), None);
+    assert_eq!(v.slice_mut(2..2).last(), None);
+}
+
+#[test]
+#[should_panic]
+fn set_out_of_bounds() {
+    let mut v = packed_ints![100, 200, 300; U9];
+    v.slice_mut(1..).set(2, 0);
+}
+
+#[test]
+#[should_panic]
+fn set_out_of_range() {
+    let mut v = packed_ints![100, 200, 300; U9];
+    v.slice_mut(1..).set(0, 512);
+}
+
+#[test]
+fn slice_mut_nested() {
+    let mut v = zeros::<U9, u32>(100);
+    v.slice_mut(7..93)
+        .slice_mut(11..)
+        .slice_mut(..50)
+        .slice_mut(3..=40)
+        .fill(511);
+
+    for (i, value) in v.iter().enumerate() {
+        assert_eq!(value, if (21..59).contains(&i) { 511 } else { 0 });
+    }
+}
+
+#[test]
+#[should_panic]
+fn slice_mut_out_of_range() {
+    let mut v = packed_ints![100, 200, 300; U9];
+    let _s = v.slice_mut(1..).slice_mut(..3);
+}
+
+#[test]
+fn split_at_mut_shared_cell() {
+    // The first three integers end at bit 27 of the first cell, which both halves then share.
+    let mut v = packed_ints![1, 2, 3, 4, 5, 6; U9];
+    let (mut lo, mut hi) = v.split_at_mut(3);
+    lo.set(2, 511);
+    hi.set(0, 510);
+    lo.fill(509);
+
+    assert_eq!(hi.iter().collect::<Vec<_>>(), vec![510, 5, 6]);
+    assert_eq!(v, packed_ints![509, 509, 509, 510, 5, 6; U9]);
+}
+
+#[test]
+fn split_at_mut_ends() {
+    let mut v = packed_ints![100, 200, 300; U9];
+    let (lo, hi) = v.split_at_mut(0);
+
+    assert!(lo.is_empty());
+    assert_eq!(hi.len(), 3);
+
+    let (lo, hi) = v.split_at_mut(3);
+
+    assert_eq!(lo.len(), 3);
+    assert!(hi.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn split_at_mut_gt() {
+    let mut v = packed_ints![100, 200, 300; U9];
+    let _s = v.split_at_mut(4);
+}
+
+#[test]
+fn split_at_mut_threads() {
+    fn fill<T: PackedInt<Value = u32>, W: Word>(s: PackedSliceMut<'_, T, W>, value: u32) {
+        let mut s = s;
+        if s.len() <= 7 {
+            s.fill(value % (T::MAX + 1));
+            return;
+        }
+        let mid = s.len() / 2;
+        let (lo, hi) = s.split_at_mut(mid);
+        std::thread::scope(|scope| {
+            scope.spawn(|| fill(lo, value * 2));
+            scope.spawn(|| fill(hi, value * 2 + 1));
+        });
+    }
+
+    for _ in 0..10 {
+        let mut v = zeros::<U7, u8>(300);
+        let mut expected = zeros::<U7, u8>(300);
+        fill(v.slice_mut(..), 1);
+        fill_serial(&mut expected);
+
+        assert_eq!(v, expected);
+    }
+
+    fn fill_serial(v: &mut PackedIntegers<U7, u8>) {
+        fn go(v: &mut PackedIntegers<U7, u8>, start: usize, end: usize, value: u32) {
+            if end - start <= 7 {
+                for i in start..end {
+                    v.set(i, value % 128);
+                }
+                return;
+            }
+            let mid = start + (end - start) / 2;
+            go(v, start, mid, value * 2);
+            go(v, mid, end, value * 2 + 1);
+        }
+        let len = v.len();
+        go(v, 0, len, 1);
+    }
+}
+
+#[test]
+fn swap() {
+    let mut v = packed_ints![100, 200, 300, 400; U9];
+    v.slice_mut(1..).swap(0, 2);
+
+    assert_eq!(v, packed_ints![100, 400, 300, 200; U9]);
+}
+
+#[test]
+fn to_packed() {
+    let mut v = packed_ints![507, 508, 509, 510, 511; U9];
+
+    assert_eq!(v.slice_mut(3..).to_packed(), packed_ints![510, 511; U9]);
+    assert_eq!(v.slice_mut(5..).to_packed(), PackedIntegers::<U9>::new());
+}
+
+#[test]
+fn word_u64() {
+    let mut v = zeros::<U33, u64>(10);
+    let (mut lo, mut hi) = v.split_at_mut(5);
+    lo.fill(U33::MAX);
+    hi.fill(1);
+    lo.set(4, 2);
+
+    assert_eq!(
+        v.iter().collect::<Vec<_>>(),
+        vec![U33::MAX, U33::MAX, U33::MAX, U33::MAX, 2, 1, 1, 1, 1, 1]
+    );
+}
+
+#[test]
+fn word_u8() {
+    let mut v = zeros::<U5, u8>(20);
+    let mut s = v.slice_mut(3..17);
+    let (mut lo, mut hi) = s.split_at_mut(3);
+    lo.fill(31);
+    hi.fill(17);
+
+    assert_eq!(
+        v.iter().collect::<Vec<_>>(),
+        [vec![0; 3], vec![31; 3], vec![17; 11], vec![0; 3]].concat()
+    );
+}
@@ -0,0 +1,91 @@
+use packed_integers::*;
+
+#[test]
+fn disjoint_runs() {
+    let a: PackedIntegers<U12> = (0..100).collect();
+    let b: PackedIntegers<U12> = (100..200).collect();
+
+    assert_eq!(a.union(&b), (0..200).collect());
+    assert!(a.intersection(&b).is_empty());
+    assert_eq!(a.difference(&b), a);
+    assert_eq!(a.symmetric_difference(&b), (0..200).collect());
+    assert_eq!(b.union_count(&a), 200);
+    assert_eq!(b.intersection_count(&a), 0);
+}
+
+#[test]
+fn duplicates() {
+    let a = packed_ints![1, 1, 1, 2, 4, 4; U9];
+    let b = packed_ints![1, 4, 4, 4, 5, 5; U9];
+
+    assert_eq!(a.union(&b), packed_ints![1, 2, 4, 5; U9]);
+    assert_eq!(a.intersection(&b), packed_ints![1, 4; U9]);
+    assert_eq!(a.difference(&b), packed_ints![2; U9]);
+    assert_eq!(b.difference(&a), packed_ints![5; U9]);
+    assert_eq!(a.symmetric_difference(&b), packed_ints![2, 5; U9]);
+}
+
+#[test]
+fn empty() {
+    let a = PackedIntegers::<U9>::new();
+    let b = packed_ints![1, 2, 2; U9];
+
+    assert_eq!(a.union(&b), packed_ints![1, 2; U9]);
+    assert_eq!(b.union(&a), packed_ints![1, 2; U9]);
+    assert!(a.intersection(&b).is_empty());
+    assert!(a.difference(&b).is_empty());
+    assert_eq!(b.difference(&a), packed_ints![1, 2; U9]);
+    assert_eq!(a.union_count(&a), 0);
+}
+
+#[test]
+fn identical() {
+    let a = packed_ints![3, 6, 9, 12; U9];
+
+    assert_eq!(a.union(&a), a);
+    assert_eq!(a.intersection(&a), a);
+    assert!(a.difference(&a).is_empty());
+    assert_eq!(a.symmetric_difference_count(&a), 0);
+}
+
+#[test]
+fn interleaved() {
+    let a = packed_ints![1, 3, 5, 7, 9; U9];
+    let b = packed_ints![2, 3, 4, 9, 10; U9];
+
+    assert_eq!(a.union(&b), packed_ints![1, 2, 3, 4, 5, 7, 9, 10; U9]);
+    assert_eq!(a.intersection(&b), packed_ints![3, 9; U9]);
+    assert_eq!(a.difference(&b), packed_ints![1, 5, 7; U9]);
+    assert_eq!(b.difference(&a), packed_ints![2, 4, 10; U9]);
+    assert_eq!(
+        a.symmetric_difference(&b),
+        packed_ints![1, 2, 4, 5, 7, 10; U9]
+    );
+    assert_eq!(a.union_count(&b), 8);
+    assert_eq!(a.intersection_count(&b), 2);
+    assert_eq!(a.difference_count(&b), 3);
+    assert_eq!(a.symmetric_difference_count(&b), 6);
+}
+
+#[test]
+fn skewed_sizes() {
+    // Three integers against a run of 2000, so each step through the run is a long gallop.
+    let a = packed_ints![5, 1000, 3000; U12];
+    let b: PackedIntegers<U12> = (1000..3000).collect();
+
+    let mut union = packed_ints![5; U12];
+    union.extend(1000..=3000);
+    let mut symmetric_difference = packed_ints![5; U12];
+    symmetric_difference.extend(1001..=3000);
+
+    assert_eq!(a.intersection(&b), packed_ints![1000; U12]);
+    assert_eq!(b.intersection(&a), packed_ints![1000; U12]);
+    assert_eq!(a.difference(&b), packed_ints![5, 3000; U12]);
+    assert_eq!(b.difference(&a), (1001..3000).collect());
+    assert_eq!(a.union(&b), union);
+    assert_eq!(b.symmetric_difference(&a), symmetric_difference);
+    assert_eq!(a.union_count(&b), 2002);
+    assert_eq!(a.intersection_count(&b), 1);
+    assert_eq!(b.difference_count(&a), 1999);
+    assert_eq!(a.symmetric_difference_count(&b), 2001);
+}